@@ -0,0 +1,213 @@
+use std::ops::Sub;
+
+/// Iterator adaptors shared by the days, available on every iterator once the trait is imported:
+///
+/// ```
+/// use adaptors::IteratorExt;
+///
+/// assert_eq!([1, 2, 3].into_iter().skip_nth(1).collect::<Vec<_>>(), [1, 3]);
+/// assert_eq!([1, 4, 6].into_iter().diffs().collect::<Vec<_>>(), [3, 2]);
+/// ```
+pub trait IteratorExt: Iterator + Sized {
+    /// Every item but the `n`th one (counting from 0)
+    fn skip_nth(self, n: usize) -> SkipNth<Self> {
+        SkipNth { it: self, idx: 0, skip: n }
+    }
+
+    /// Every item whose position is not one of `indices`
+    fn skip_indices<S: IntoIterator<Item = usize>>(self, indices: S) -> SkipIndices<Self> {
+        let mut indices = indices.into_iter().collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        SkipIndices { it: self, idx: 0, indices, next: 0 }
+    }
+
+    /// Every subsequence with one item left out, in order: without the first item, without the second...
+    /// Each one is a lazy clone of this iterator, so nothing is allocated.
+    fn leave_one_out(self) -> LeaveOneOut<Self>
+    where Self: Clone {
+        let len = self.clone().count();
+        LeaveOneOut { it: self, idx: 0, len }
+    }
+
+    /// Difference between each item and the previous one (ie: the steps between levels)
+    fn diffs(self) -> Diffs<Self>
+    where Self::Item: Copy + Sub {
+        Diffs { it: self, previous: None }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+
+// ------------ SkipNth class ------------
+/// See `IteratorExt::skip_nth`
+#[derive(Clone, Debug)]
+pub struct SkipNth<I> {
+    it: I,
+    idx: usize,
+    skip: usize
+}
+
+impl<I: Iterator> Iterator for SkipNth<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.skip {
+            _ = self.it.next();
+        }
+        self.idx += 1;
+        self.it.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.it.size_hint();
+        match self.idx <= self.skip {
+            true  => (low.saturating_sub(1), high),
+            false => (low, high),
+        }
+    }
+}
+
+
+// ------------ SkipIndices class ------------
+/// See `IteratorExt::skip_indices`
+#[derive(Clone, Debug)]
+pub struct SkipIndices<I> {
+    it: I,
+    idx: usize,
+    /// Sorted positions to skip, and the first of them not reached yet
+    indices: Vec<usize>,
+    next: usize
+}
+
+impl<I: Iterator> Iterator for SkipIndices<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.indices.get(self.next) == Some(&self.idx) {
+            _ = self.it.next()?;
+            self.idx += 1;
+            self.next += 1;
+        }
+        self.idx += 1;
+        self.it.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Skipped positions among the next `n` items of the inner iterator
+        let skipped = |n: usize| self.indices[self.next..].partition_point(|&idx| idx < self.idx.saturating_add(n));
+        let (low, high) = self.it.size_hint();
+        (low - skipped(low), high.map( |high| high - skipped(high) ))
+    }
+}
+
+
+// ------------ LeaveOneOut class ------------
+/// See `IteratorExt::leave_one_out`
+#[derive(Clone, Debug)]
+pub struct LeaveOneOut<I> {
+    it: I,
+    idx: usize,
+    len: usize
+}
+
+impl<I: Iterator + Clone> Iterator for LeaveOneOut<I> {
+    type Item = SkipNth<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.len {
+            return None;
+        }
+        self.idx += 1;
+        Some( self.it.clone().skip_nth(self.idx - 1) )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.idx, Some(self.len - self.idx))
+    }
+}
+
+impl<I: Iterator + Clone> ExactSizeIterator for LeaveOneOut<I> {}
+
+
+// ------------ Diffs class ------------
+/// See `IteratorExt::diffs`
+#[derive(Clone, Debug)]
+pub struct Diffs<I: Iterator> {
+    it: I,
+    previous: Option<I::Item>
+}
+
+impl<I> Iterator for Diffs<I>
+where I: Iterator,
+      I::Item: Copy + Sub
+{
+    type Item = <I::Item as Sub>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.previous.is_none() {
+            self.previous = self.it.next();
+        }
+        let current = self.it.next()?;
+        let previous = self.previous.replace(current)?;
+        Some(current - previous)
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_adaptors() {
+        assert_eq!((0..5).skip_nth(0).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!((0..5).skip_nth(9).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert_eq!((0..5).skip_indices([4, 0, 2, 2, 7]).collect::<Vec<_>>(), [1, 3]);
+        let mut skipped = (0..5).skip_indices([4, 0, 2, 2, 7]);
+        assert_eq!(skipped.size_hint(), (2, Some(2)));
+        skipped.next();
+        assert_eq!(skipped.size_hint(), (1, Some(1)));
+        assert_eq!((0..).skip_indices([1, 3]).size_hint(), (usize::MAX - 2, None));
+        assert_eq!(
+            (0..3).leave_one_out().map(|it| it.collect::<Vec<_>>()).collect::<Vec<_>>(),
+            [vec![1, 2], vec![0, 2], vec![0, 1]]
+        );
+        assert_eq!((0..0).leave_one_out().len(), 0);
+        assert_eq!([5, 3, 8].iter().diffs().collect::<Vec<_>>(), [-2, 5]);
+        assert_eq!([5].into_iter().diffs().count(), 0);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_against_vectors(
+            items in proptest::collection::vec(-50..50_i32, 0..12),
+            indices in proptest::collection::vec(0..15_usize, 0..5)
+        ) {
+            let without = |skipped: &[usize]| items.iter().enumerate()
+                .filter(|(idx, _)| !skipped.contains(idx))
+                .map(|(_, &item)| item)
+                .collect::<Vec<_>>();
+
+            for &idx in &indices {
+                proptest::prop_assert_eq!(items.iter().copied().skip_nth(idx).collect::<Vec<_>>(), without(&[idx]));
+            }
+            proptest::prop_assert_eq!(items.iter().copied().skip_indices(indices.clone()).collect::<Vec<_>>(), without(&indices));
+            let len = without(&indices).len();
+            proptest::prop_assert_eq!(items.iter().skip_indices(indices.clone()).size_hint(), (len, Some(len)));
+
+            let subsequences = items.iter().copied().leave_one_out();
+            proptest::prop_assert_eq!(subsequences.len(), items.len());
+            for (idx, subsequence) in subsequences.enumerate() {
+                proptest::prop_assert_eq!(subsequence.collect::<Vec<_>>(), without(&[idx]));
+            }
+
+            let diffs = items.windows(2).map(|pair| pair[1] - pair[0]).collect::<Vec<_>>();
+            proptest::prop_assert_eq!(items.iter().copied().diffs().collect::<Vec<_>>(), diffs);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use solution::Solution;
+
+use aoc::registry::day;
+
+/// Benchmark parse, part1 and part2 of a day on its real input
+fn bench_day<S: Solution>(c: &mut Criterion, number: u32) {
+    let day = day(number).unwrap();
+    let text = fs::read_to_string(day.input_path())
+        .expect("Should have been able to read the file");
+    let input = S::parse(&text).unwrap();
+    let params = S::Params::default();
+
+    let mut group = c.benchmark_group(format!("day{number}"));
+    // Some parts take seconds, keep the minimum amount of samples
+    group.sample_size(10);
+
+    group.bench_function("parse",
+        |b| b.iter(|| S::parse(black_box(&text))));
+
+    group.bench_function("part1",
+        |b| b.iter(|| S::part1(black_box(&input), &params)));
+
+    group.bench_function("part2",
+        |b| b.iter(|| S::part2(black_box(&input), &params)));
+
+    group.finish();
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, 1);
+    bench_day::<day2::Day2>(c, 2);
+    bench_day::<day3::Day3>(c, 3);
+    bench_day::<day4::Day4>(c, 4);
+    bench_day::<day5::Day5>(c, 5);
+    bench_day::<day6::Day6>(c, 6);
+    bench_day::<day7::Day7>(c, 7);
+    bench_day::<day8::Day8>(c, 8);
+    bench_day::<day9::Day9>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+    bench_day::<day11::Day11>(c, 11);
+    bench_day::<day12::Day12>(c, 12);
+    bench_day::<day13::Day13>(c, 13);
+    bench_day::<day14::Day14>(c, 14);
+    bench_day::<day15::Day15>(c, 15);
+    bench_day::<day16::Day16>(c, 16);
+    bench_day::<day17::Day17>(c, 17);
+    bench_day::<day18::Day18>(c, 18);
+    bench_day::<day19::Day19>(c, 19);
+    bench_day::<day20::Day20>(c, 20);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
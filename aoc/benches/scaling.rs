@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use solution::Solution;
+
+/// Benchmark part1 and part2 of a day on generated inputs of growing size
+fn bench_scaling<S: Solution>(c: &mut Criterion, number: u32, sizes: &[usize], generate: impl Fn(&mut ChaCha8Rng, usize) -> String) {
+    let params = S::Params::default();
+
+    let mut group = c.benchmark_group(format!("day{number}"));
+    group.sample_size(10);
+
+    for &size in sizes {
+        let text = generate(&mut ChaCha8Rng::seed_from_u64(size as u64), size);
+        let input = S::parse(&text).unwrap();
+        group.throughput(Throughput::Bytes(text.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &input,
+            |b, input| b.iter(|| S::part1(black_box(input), &params)));
+
+        group.bench_with_input(BenchmarkId::new("part2", size), &input,
+            |b, input| b.iter(|| S::part2(black_box(input), &params)));
+    }
+
+    group.finish();
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_scaling::<day1::Day1>(c, 1, &[1000, 10_000, 100_000],
+        |rng, pairs| day1::generator::generate(rng, &day1::generator::Options { pairs, ..Default::default() }));
+    bench_scaling::<day2::Day2>(c, 2, &[1000, 10_000, 100_000],
+        |rng, reports| day2::generator::generate(rng, &day2::generator::Options { reports, ..Default::default() }));
+    bench_scaling::<day5::Day5>(c, 5, &[200, 2000, 20_000],
+        |rng, updates| day5::generator::generate(rng, &day5::generator::Options { updates, ..Default::default() }));
+    bench_scaling::<day9::Day9>(c, 9, &[1000, 5000, 20_000],
+        |rng, files| day9::generator::generate(rng, &day9::generator::Options { files, ..Default::default() }));
+    bench_scaling::<day16::Day16>(c, 16, &[31, 71, 141],
+        |rng, size| day16::generator::generate(rng, &day16::generator::Options { width: size, height: size, ..Default::default() }));
+    bench_scaling::<day19::Day19>(c, 19, &[100, 400, 1600],
+        |rng, designs| day19::generator::generate(rng, &day19::generator::Options { designs, ..Default::default() }));
+    bench_scaling::<day20::Day20>(c, 20, &[31, 71, 141],
+        |rng, size| day20::generator::generate(rng, &day20::generator::Options { width: size, height: size }));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+
+use trace::Filter;
+
+use crate::report::{OutputFormat, ReportFormat};
+
+pub static USAGE: &str = "Usage:
+    aoc run <day...> [--time] [--report <file>] [--format <text|json>] [--trace <day[=level]>...]
+    aoc run --all [--time] [--report <file>] [--format <text|json>] [--trace <day[=level]>...]
+    aoc verify [day...] [--trace <day[=level]>...]
+    aoc fetch <day...>
+    aoc submit <day> <part>
+    aoc new <day> [--rectangle]
+
+Commands:
+    run       Solve the real input of the given days
+    verify    Solve every day (or the given ones) and compare with data/answers.txt. Days without
+              an input are reported separately from wrong answers, and also fail
+    fetch     Download the input of the given days into dayN/data/input.txt, unless it is already there.
+              Needs the session cookie in AOC_SESSION or in the .session file
+    submit    Solve a day and submit the answer of one part, unless dayN/data/history.txt shows that
+              it is too early or that the answer cannot be right. Also needs the session cookie
+    new       Create the crate of a day from a template, with a sample test and a bench stub,
+              and add it to the workspace, the runner and the benches
+
+Options:
+    --all              Run every day and print a table
+    --time             Print how long parsing and each part took
+    --report <file>    Save the timings as JSON or CSV, depending on the extension
+    --format <format>  Print the answers as text (default) or as one JSON object per day, with timings.
+                       Errors always go to stderr
+    --trace <filter>   Print the trace events of a day (ie: `day9`, `day9=trace` or `all=info`) on stderr.
+                       Levels are info, debug (default) and trace. Needs a build with `--features trace`
+    --rectangle        Parse the new day into a `Rectangle<char>`";
+
+
+// ------------ Options class ------------
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Command {
+    Run,
+    Verify,
+    Fetch,
+    Submit,
+    New
+}
+
+/// Parsed command line
+#[derive(PartialEq, Debug)]
+pub struct Options {
+    pub command: Command,
+    /// Selected days, empty if none was given
+    pub days: Vec<u32>,
+    pub all: bool,
+    pub time: bool,
+    pub report: Option<(PathBuf, ReportFormat)>,
+    pub format: OutputFormat,
+    /// Days whose trace events are printed
+    pub trace: Vec<Filter>,
+    /// Part to submit
+    pub part: Option<u8>,
+    /// Whether the new day depends on `rectangle`
+    pub rectangle: bool
+}
+
+/// Parse `6` or `day6` into a day number
+fn parse_day(arg: &str) -> Result<u32, String> {
+    arg.trim_start_matches("day")
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid day `{arg}`"))
+}
+
+/// Parse `1`, `2`, `part1` or `part2` into a part number
+fn parse_part(arg: &str) -> Result<u8, String> {
+    match arg.trim_start_matches("part") {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _   => Err(format!("Invalid part `{arg}`")),
+    }
+}
+
+impl Options {
+    /// Parse the arguments, without the program name
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut args = args.iter();
+
+        let command = match args.next().map(String::as_str) {
+            Some("run")    => Command::Run,
+            Some("verify") => Command::Verify,
+            Some("fetch")  => Command::Fetch,
+            Some("submit") => Command::Submit,
+            Some("new")    => Command::New,
+            Some(other)    => return Err(format!("Unknown command `{other}`")),
+            None           => return Err("Missing command".to_string()),
+        };
+
+        let mut options = Options { command, days: Vec::new(), all: false, time: false, report: None, format: OutputFormat::Text, trace: Vec::new(), part: None, rectangle: false };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all"    => options.all = true,
+                "--time"   => options.time = true,
+                "--rectangle" => options.rectangle = true,
+                "--report" => {
+                    let path = PathBuf::from(args.next().ok_or("Missing file after `--report`")?);
+                    let format = ReportFormat::from_path(&path)
+                        .ok_or_else(|| format!("Report `{}` must end in .json or .csv", path.display()))?;
+                    options.report = Some((path, format));
+                }
+                "--trace" => {
+                    let filter = args.next().ok_or("Missing filter after `--trace`")?;
+                    options.trace.push(Filter::parse(filter)?);
+                }
+                "--format" => {
+                    let format = args.next().ok_or("Missing format after `--format`")?;
+                    options.format = OutputFormat::from_name(format)
+                        .ok_or_else(|| format!("Format `{format}` must be `text` or `json`"))?;
+                }
+                flag if flag.starts_with("--") => return Err(format!("Unknown option `{flag}`")),
+                part if command == Command::Submit && options.days.len() == 1 && options.part.is_none() => options.part = Some(parse_part(part)?),
+                day => options.days.push(parse_day(day)?),
+            }
+        }
+
+        // Check the combination of options
+        match command {
+            Command::Run if options.days.is_empty() && !options.all =>
+                Err("`run` needs some days or `--all`".to_string()),
+            Command::Run if !options.days.is_empty() && options.all =>
+                Err("`run` takes either some days or `--all`".to_string()),
+            Command::Verify if options.all || options.time || options.report.is_some() =>
+                Err("`verify` only takes days".to_string()),
+            Command::Fetch if options.days.is_empty() =>
+                Err("`fetch` needs some days".to_string()),
+            Command::Fetch if options.all || options.time || options.report.is_some() =>
+                Err("`fetch` only takes days".to_string()),
+            Command::Submit if options.days.len() != 1 || options.part.is_none() || options.all || options.time || options.report.is_some() =>
+                Err("`submit` takes a day and a part".to_string()),
+            Command::New if options.days.len() != 1 || options.all || options.time || options.report.is_some() =>
+                Err("`new` takes a single day".to_string()),
+            _ if options.rectangle && command != Command::New =>
+                Err("`--rectangle` only applies to `new`".to_string()),
+            _ if options.format != OutputFormat::Text && command != Command::Run =>
+                Err("`--format` only applies to `run`".to_string()),
+            _ if !options.trace.is_empty() && !matches!(command, Command::Run | Command::Verify) =>
+                Err("`--trace` only applies to `run` and `verify`".to_string()),
+            _ => Ok(options),
+        }
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(&args.split_whitespace().map(str::to_string).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(parse("run 6 day11 --time"), Ok(Options {
+            command: Command::Run, days: vec![6, 11], all: false, time: true, report: None, format: OutputFormat::Text, trace: Vec::new(), part: None, rectangle: false
+        }));
+        assert_eq!(parse("run --all --report times.csv"), Ok(Options {
+            command: Command::Run, days: vec![], all: true, time: false,
+            report: Some((PathBuf::from("times.csv"), ReportFormat::Csv)), format: OutputFormat::Text, trace: Vec::new(), part: None, rectangle: false
+        }));
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(parse("verify"), Ok(Options {
+            command: Command::Verify, days: vec![], all: false, time: false, report: None, format: OutputFormat::Text, trace: Vec::new(), part: None, rectangle: false
+        }));
+        assert_eq!(parse("verify 3").unwrap().days, vec![3]);
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(parse("submit day5 part2"), Ok(Options {
+            command: Command::Submit, days: vec![5], all: false, time: false, report: None, format: OutputFormat::Text, trace: Vec::new(), part: Some(2), rectangle: false
+        }));
+        assert_eq!(parse("submit 5 1").unwrap().part, Some(1));
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse("new day21 --rectangle"), Ok(Options {
+            command: Command::New, days: vec![21], all: false, time: false, report: None, format: OutputFormat::Text, trace: Vec::new(), part: None, rectangle: true
+        }));
+        assert!(!parse("new 21").unwrap().rectangle);
+    }
+
+    #[test]
+    fn test_parse_trace() {
+        assert_eq!(parse("run 9 --trace day9 --trace day5=trace").unwrap().trace, vec![
+            Filter::parse("day9").unwrap(), Filter::parse("day5=trace").unwrap()
+        ]);
+        assert_eq!(parse("verify 9 --trace all=info").unwrap().trace, vec![Filter::parse("all=info").unwrap()]);
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse("run --all --format json").unwrap().format, OutputFormat::Json);
+        assert_eq!(parse("run 1 --format text").unwrap().format, OutputFormat::Text);
+        assert_eq!(parse("run 1").unwrap().format, OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(""), Err("Missing command".to_string()));
+        assert_eq!(parse("fly 1"), Err("Unknown command `fly`".to_string()));
+        assert_eq!(parse("run"), Err("`run` needs some days or `--all`".to_string()));
+        assert_eq!(parse("run 1 --all"), Err("`run` takes either some days or `--all`".to_string()));
+        assert_eq!(parse("run x"), Err("Invalid day `x`".to_string()));
+        assert_eq!(parse("run 1 --fast"), Err("Unknown option `--fast`".to_string()));
+        assert_eq!(parse("run 1 --report"), Err("Missing file after `--report`".to_string()));
+        assert_eq!(parse("run 1 --report t.txt"), Err("Report `t.txt` must end in .json or .csv".to_string()));
+        assert_eq!(parse("verify --time"), Err("`verify` only takes days".to_string()));
+        assert_eq!(parse("fetch"), Err("`fetch` needs some days".to_string()));
+        assert_eq!(parse("fetch 1 --all"), Err("`fetch` only takes days".to_string()));
+        assert_eq!(parse("submit 5"), Err("`submit` takes a day and a part".to_string()));
+        assert_eq!(parse("submit 5 3"), Err("Invalid part `3`".to_string()));
+        assert_eq!(parse("submit 5 1 2"), Err("`submit` takes a day and a part".to_string()));
+        assert_eq!(parse("new"), Err("`new` takes a single day".to_string()));
+        assert_eq!(parse("new 21 22"), Err("`new` takes a single day".to_string()));
+        assert_eq!(parse("run 1 --rectangle"), Err("`--rectangle` only applies to `new`".to_string()));
+        assert_eq!(parse("run 1 --format"), Err("Missing format after `--format`".to_string()));
+        assert_eq!(parse("run 1 --format xml"), Err("Format `xml` must be `text` or `json`".to_string()));
+        assert_eq!(parse("verify --format json"), Err("`--format` only applies to `run`".to_string()));
+        assert_eq!(parse("run 1 --trace"), Err("Missing filter after `--trace`".to_string()));
+        assert_eq!(parse("fetch 1 --trace day1"), Err("`--trace` only applies to `run` and `verify`".to_string()));
+    }
+}
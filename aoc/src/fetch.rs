@@ -0,0 +1,118 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::http::{Http, BASE_URL};
+use crate::registry::workspace_root;
+use crate::{read, AocError};
+
+/// Edition of the puzzles in this workspace
+pub static YEAR: u32 = 2024;
+
+/// Environment variable holding the session cookie
+pub static SESSION_VAR: &str = "AOC_SESSION";
+
+
+// ------------ Session ------------
+/// Session cookie of the website, from `AOC_SESSION` or the `.session` file in the workspace root
+pub fn session_token() -> Result<String, AocError> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let path = workspace_root().join(".session");
+    match path.exists() {
+        true  => Ok(read(&path)?.trim().to_string()),
+        false => Err(AocError::MissingSession),
+    }
+}
+
+/// First line of a response body, to show in error messages
+pub fn first_line(body: &str) -> String {
+    body.lines().next().unwrap_or("").trim().to_string()
+}
+
+
+// ------------ Fetch ------------
+/// What `fetch` did to get an input
+#[derive(PartialEq, Eq, Debug)]
+pub enum Fetched {
+    /// The input was already there, it was not downloaded again
+    Cached(PathBuf),
+    Downloaded(PathBuf)
+}
+
+pub fn input_url(number: u32) -> String {
+    format!("{BASE_URL}/{YEAR}/day/{number}/input")
+}
+
+/// Download the input of a day into `path`, unless it is already there.
+/// The session is only asked for when a download is needed.
+pub fn fetch<F>(http: &dyn Http, number: u32, path: &Path, session: F) -> Result<Fetched, AocError>
+where F: FnOnce() -> Result<String, AocError>
+{
+    if !(1..=25).contains(&number) {
+        return Err(AocError::UnknownDay(number));
+    }
+    if path.exists() {
+        return Ok( Fetched::Cached(path.to_path_buf()) );
+    }
+
+    let url = input_url(number);
+    let response = http.get(&url, &session()?)?;
+    if response.status != 200 {
+        return Err(AocError::Http { url, status: response.status, message: first_line(&response.body) });
+    }
+
+    // Only create the file once the download succeeded, so failures are retried
+    let write_error = |source| AocError::Io { path: path.to_path_buf(), source };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(write_error)?;
+    }
+    fs::write(path, response.body).map_err(write_error)?;
+
+    Ok( Fetched::Downloaded(path.to_path_buf()) )
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::http::fake::{FakeHttp, TempDir};
+
+    #[test]
+    fn test_fetch_downloads_once() {
+        let http = FakeHttp::default().with(&input_url(3), 200, "mul(2,4)\n");
+        let dir = TempDir::new("fetch-once");
+        let path = dir.0.join("data/input.txt");
+
+        assert_eq!(fetch(&http, 3, &path, || Ok("abc".to_string())).unwrap(), Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mul(2,4)\n");
+
+        // Second time it is cached, and no session is needed
+        assert_eq!(fetch(&http, 3, &path, || Err(AocError::MissingSession)).unwrap(), Fetched::Cached(path.clone()));
+        assert_eq!(*http.requests.borrow(), vec![format!("GET {} session=abc", input_url(3))]);
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let http = FakeHttp::default()
+            .with(&input_url(1), 400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n");
+        let dir = TempDir::new("fetch-errors");
+        let path = dir.0.join("data/input.txt");
+
+        assert!(matches!(
+            fetch(&http, 1, &path, || Ok("bad".to_string())),
+            Err(AocError::Http { status: 400, ref message, .. }) if message.starts_with("Puzzle inputs differ")
+        ));
+        assert!(matches!(fetch(&http, 2, &path, || Ok("abc".to_string())), Err(AocError::Http { status: 404, .. })));
+        assert!(matches!(fetch(&http, 1, &path, || Err(AocError::MissingSession)), Err(AocError::MissingSession)));
+        assert!(matches!(fetch(&http, 26, &path, || Ok("abc".to_string())), Err(AocError::UnknownDay(26))));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,215 @@
+use std::io::Read;
+use std::time::Duration;
+
+use crate::AocError;
+
+/// Base address of the puzzles
+pub static BASE_URL: &str = "https://adventofcode.com";
+
+/// Asked by the site to identify automated requests
+static USER_AGENT: &str = "github.com/AsensioL/adventofcode2024 aoc runner";
+
+
+// ------------ Http trait ------------
+/// Status and body of an HTTP response, whatever the status is
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String
+}
+
+/// Minimal HTTP client, so that tests do not need the network
+pub trait Http {
+    /// GET `url` authenticated with the `session` cookie
+    fn get(&self, url: &str, session: &str) -> Result<Response, AocError>;
+
+    /// POST a url-encoded form to `url`, authenticated with the `session` cookie
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, AocError>;
+}
+
+
+// ------------ UreqHttp class ------------
+/// Real client, backed by `ureq`
+pub struct UreqHttp {
+    agent: ureq::Agent
+}
+
+impl Default for UreqHttp {
+    fn default() -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+        UreqHttp { agent }
+    }
+}
+
+/// Turn both successful and error statuses into a `Response`
+fn into_response(url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<Response, AocError> {
+    let response = match result {
+        Ok(response)                          => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(error)                            => return Err(AocError::Network(error.to_string())),
+    };
+
+    let status = response.status();
+    let mut body = String::new();
+    response.into_reader()
+        .read_to_string(&mut body)
+        .map_err(|error| AocError::Network(format!("{url}: {error}")))?;
+
+    Ok( Response { status, body } )
+}
+
+impl Http for UreqHttp {
+    fn get(&self, url: &str, session: &str) -> Result<Response, AocError> {
+        let result = self.agent.get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+        into_response(url, result)
+    }
+
+    fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, AocError> {
+        let result = self.agent.post(url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(form);
+        into_response(url, result)
+    }
+}
+
+
+// ------------ FakeHttp class ------------
+/// In-memory server for tests: answers with canned responses and records the requests
+#[cfg(test)]
+pub mod fake {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[derive(Default)]
+    pub struct FakeHttp {
+        pub responses: HashMap<String, Response>,
+        pub requests: RefCell<Vec<String>>
+    }
+
+    impl FakeHttp {
+        pub fn with(mut self, url: &str, status: u16, body: &str) -> Self {
+            self.responses.insert(url.to_string(), Response { status, body: body.to_string() });
+            self
+        }
+    }
+
+    impl Http for FakeHttp {
+        fn get(&self, url: &str, session: &str) -> Result<Response, AocError> {
+            self.requests.borrow_mut().push(format!("GET {url} session={session}"));
+            Ok( self.respond(url) )
+        }
+
+        fn post_form(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, AocError> {
+            let form = form.iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("&");
+            self.requests.borrow_mut().push(format!("POST {url} session={session} {form}"));
+            Ok( self.respond(url) )
+        }
+    }
+
+    impl FakeHttp {
+        fn respond(&self, url: &str) -> Response {
+            self.responses.get(url)
+                .cloned()
+                .unwrap_or(Response { status: 404, body: "Not Found".to_string() })
+        }
+    }
+
+    /// Directory of a test, emptied when created and removed when dropped
+    pub struct TempDir(pub PathBuf);
+
+    impl TempDir {
+        pub fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("aoc-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single request on a local port with a canned response, and return the request received
+    fn stub_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            // Headers, then the body if there is one
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body_received = vec![0; content_length];
+            reader.read_exact(&mut body_received).unwrap();
+            request.push_str(&String::from_utf8(body_received).unwrap());
+
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_ureq_get() {
+        let (url, server) = stub_server(200, "1 2 3\n");
+        let response = UreqHttp::default().get(&format!("{url}/2024/day/1/input"), "abc").unwrap();
+        assert_eq!(response, Response { status: 200, body: "1 2 3\n".to_string() });
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn test_ureq_post_form() {
+        let (url, server) = stub_server(400, "Bad");
+        let response = UreqHttp::default().post_form(&format!("{url}/2024/day/1/answer"), "abc", &[("level", "1"), ("answer", "42")]).unwrap();
+        assert_eq!(response, Response { status: 400, body: "Bad".to_string() });
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("level=1&answer=42"));
+    }
+}
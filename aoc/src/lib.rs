@@ -0,0 +1,153 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use parsing::ParseError;
+use solution::{fixtures, Answer, Fixture, INPUT_FIXTURE};
+
+pub mod cli;
+pub mod fetch;
+pub mod http;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+
+use registry::Day;
+
+
+// ------------ AocError class ------------
+#[derive(Error, Debug)]
+pub enum AocError {
+    #[error("Cannot access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Cannot parse {path}: {source}")]
+    Parse { path: PathBuf, source: ParseError },
+    #[error("There is no day {0}")]
+    UnknownDay(u32),
+    #[error("Missing session token: set AOC_SESSION or write it into .session")]
+    MissingSession,
+    #[error("Request to {url} failed with status {status}: {message}")]
+    Http { url: String, status: u16, message: String },
+    #[error("Request failed: {0}")]
+    Network(String),
+    #[error("Not submitted: {0}")]
+    Refused(String),
+    #[error("Day {0} already exists")]
+    DayExists(u32),
+    #[error("Cannot create the day: {0}")]
+    Scaffold(String),
+}
+
+/// Read a whole file, remembering its path in case of error
+pub fn read(path: &Path) -> Result<String, AocError> {
+    fs::read_to_string(path)
+        .map_err(|source| AocError::Io { path: path.to_path_buf(), source })
+}
+
+
+// ------------ Verification ------------
+/// Result of comparing one computed answer against the registry
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    Correct,
+    Wrong { expected: String },
+    /// No known answer to compare with
+    Unknown,
+    /// The day could not solve the part on this input
+    Unsolved
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Check {
+    pub day: u32,
+    pub fixture: String,
+    pub part: usize,
+    pub answer: Answer,
+    pub outcome: Outcome
+}
+
+impl Check {
+    pub fn new(day: u32, fixture: &Fixture, part: usize, answer: Answer) -> Check {
+        let outcome = match &fixture.answers[part - 1] {
+            _ if !answer.is_solved()                       => Outcome::Unsolved,
+            None                                           => Outcome::Unknown,
+            Some(expected) if *expected == answer.to_string() => Outcome::Correct,
+            Some(expected)                                 => Outcome::Wrong { expected: expected.clone() },
+        };
+        Check { day, fixture: fixture.name.clone(), part, answer, outcome }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self.outcome, Outcome::Wrong { .. } | Outcome::Unsolved)
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = format!("day{:<2} {:<14} part{}", self.day, self.fixture, self.part);
+        match &self.outcome {
+            Outcome::Correct             => write!(f, "{label}  ok       {}", self.answer),
+            Outcome::Wrong { expected }  => write!(f, "{label}  WRONG    {} (expected {expected})", self.answer),
+            Outcome::Unknown             => write!(f, "{label}  unknown  {}", self.answer),
+            Outcome::Unsolved            => write!(f, "{label}  FAILED   {}", self.answer),
+        }
+    }
+}
+
+/// Solve every fixture of a day listed in its answers file, plus the real input, and compare.
+/// Parts of the fixtures without an expected answer are skipped.
+/// A missing answers file means every answer of the real input is unknown,
+/// and a missing real input is skipped (see `Day::has_input`).
+pub fn verify(day: &Day) -> Result<Vec<Check>, AocError> {
+    let answers_path = day.answers_path();
+    let mut fixtures = match answers_path.exists() {
+        true  => fixtures(&read(&answers_path)?)
+                    .map_err(|source| AocError::Parse { path: answers_path, source })?,
+        false => Vec::new(),
+    };
+    if !fixtures.iter().any(|fixture| fixture.name == INPUT_FIXTURE) {
+        fixtures.push(Fixture { name: INPUT_FIXTURE.to_string(), ..Default::default() });
+    }
+
+    let mut checks = Vec::new();
+    for fixture in &fixtures {
+        let path = match fixture.name == INPUT_FIXTURE {
+            true if !day.has_input() => continue,
+            true                     => day.input_path(),
+            false                    => day.fixture_path(&fixture.name),
+        };
+        let answers = (day.solve_fixture)(&read(&path)?, fixture)
+            .map_err(|source| AocError::Parse { path, source })?;
+        for (part, answer) in (1..).zip(answers) {
+            if let Some(answer) = answer {
+                checks.push(Check::new(day.number, fixture, part, answer));
+            }
+        }
+    }
+
+    Ok(checks)
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let input = Fixture { name: "input".to_string(), params: None, answers: [Some("42".to_string()), Some("6,1".to_string())] };
+        let sample = Fixture { name: "sample".to_string(), ..Default::default() };
+        assert_eq!(Check::new(1, &input, 1, Answer::Signed(42)).outcome, Outcome::Correct);
+        assert_eq!(Check::new(1, &input, 2, "6,2".into()).outcome, Outcome::Wrong { expected: "6,1".to_string() });
+        assert_eq!(Check::new(1, &sample, 1, Answer::Signed(42)).outcome, Outcome::Unknown);
+        assert!(Check::new(1, &sample, 1, Answer::Unsolved("no path".to_string())).is_wrong());
+        assert!(Check::new(1, &input, 2, "6,2".into()).is_wrong());
+    }
+}
@@ -0,0 +1,237 @@
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc::cli::{Command, Options, USAGE};
+use aoc::fetch::{fetch, session_token, Fetched};
+use aoc::http::UreqHttp;
+use aoc::registry::{day, days, history_path, input_path, workspace_root, Day, Run};
+use aoc::report::{current_commit, json_line, OutputFormat, Report};
+use aoc::scaffold::new_day;
+use aoc::submit::submit;
+use aoc::{read, verify, AocError};
+
+/// Selected days, or all of them if none was given
+fn selected_days(numbers: &[u32]) -> Result<Vec<Day>, AocError> {
+    if numbers.is_empty() {
+        return Ok(days());
+    }
+
+    numbers.iter()
+        .map(|&number| day(number).ok_or(AocError::UnknownDay(number)))
+        .collect()
+}
+
+/// Solve the real input of a day
+fn solve(day: &Day) -> Result<Run, AocError> {
+    let path = day.input_path();
+    (day.solve)(&read(&path)?)
+        .map_err(|source| AocError::Parse { path, source })
+}
+
+fn print_run(number: u32, run: &Run, time: bool) {
+    println!("Day {number}");
+    println!("Part 1, solution: {}", run.part1);
+    println!("Part 2, solution: {}", run.part2);
+    if time {
+        let t = &run.timings;
+        println!("Time: parse {:.2?}, part 1 {:.2?}, part 2 {:.2?}, total {:.2?}", t.parse, t.part1, t.part2, t.total());
+    }
+}
+
+fn print_table_row(number: u32, run: &Run, time: bool) {
+    print!("{:>3}  {:>20}  {:>20}", number, run.part1, run.part2);
+    if time {
+        let t = &run.timings;
+        print!("  {:>10.2?}  {:>10.2?}  {:>10.2?}  {:>10.2?}", t.parse, t.part1, t.part2, t.total());
+    }
+    println!();
+}
+
+fn run_days(options: &Options) -> ExitCode {
+    let days = match selected_days(&options.days) {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let text = options.format == OutputFormat::Text;
+    if options.all && text {
+        print!("{:>3}  {:>20}  {:>20}", "day", "part 1", "part 2");
+        if options.time {
+            print!("  {:>10}  {:>10}  {:>10}  {:>10}", "parse", "part 1", "part 2", "total");
+        }
+        println!();
+    }
+
+    let mut report = Report { commit: current_commit(), days: Vec::new() };
+    let mut failures = 0;
+    for day in &days {
+        match solve(day) {
+            Ok(run) => {
+                match (options.format, options.all) {
+                    (OutputFormat::Json, _)     => println!("{}", json_line(day.number, &run)),
+                    (OutputFormat::Text, true)  => print_table_row(day.number, &run, options.time),
+                    (OutputFormat::Text, false) => print_run(day.number, &run, options.time),
+                }
+                report.days.push((day.number, run));
+            }
+            Err(error) => {
+                eprintln!("day{:<2} {error}", day.number);
+                failures += 1;
+            }
+        }
+    }
+
+    if options.all && options.time && text {
+        let total = report.days.iter().map(|(_, run)| run.timings.total()).sum::<Duration>();
+        println!("{:>3}  {:>20}  {:>20}  {:>10}  {:>10}  {:>10}  {:>10.2?}", "all", "", "", "", "", "", total);
+    }
+
+    if let Some((path, format)) = &options.report {
+        if let Err(error) = fs::write(path, report.render(*format)) {
+            eprintln!("Cannot write {}: {error}", path.display());
+            failures += 1;
+        }
+    }
+
+    match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+fn verify_days(options: &Options) -> ExitCode {
+    let days = match selected_days(&options.days) {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut failures = 0;
+    let mut missing_inputs = 0;
+    for day in &days {
+        match verify(day) {
+            Ok(checks) => {
+                for check in checks {
+                    println!("{check}");
+                    failures += check.is_wrong() as usize;
+                }
+                if !day.has_input() {
+                    eprintln!("day{:<2} no input in {}, run `aoc fetch {}`", day.number, day.input_path().display(), day.number);
+                    missing_inputs += 1;
+                }
+            }
+            Err(error) => {
+                eprintln!("day{:<2} {error}", day.number);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} answer(s) did not match or could not be computed");
+    }
+    if missing_inputs > 0 {
+        eprintln!("{missing_inputs} day(s) without an input to verify");
+    }
+    match failures + missing_inputs {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+fn fetch_days(options: &Options) -> ExitCode {
+    let http = UreqHttp::default();
+
+    let mut failures = 0;
+    for &number in &options.days {
+        match fetch(&http, number, &input_path(number), session_token) {
+            Ok(Fetched::Cached(path))     => println!("day{number:<2} already in {}", path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("day{number:<2} downloaded into {}", path.display()),
+            Err(error) => {
+                eprintln!("day{number:<2} {error}");
+                failures += 1;
+            }
+        }
+    }
+
+    match failures {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+fn submit_answer(options: &Options) -> Result<(), AocError> {
+    let number = options.days[0];
+    let part = options.part.unwrap();
+
+    let day = day(number).ok_or(AocError::UnknownDay(number))?;
+    let run = solve(&day)?;
+    let answer = match part {
+        1 => run.part1,
+        _ => run.part2,
+    };
+    if !answer.is_solved() {
+        return Err(AocError::Refused(answer.to_string()));
+    }
+    let answer = answer.to_string();
+    println!("Day {number}, part {part}: submitting {answer}");
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let submission = submit(&UreqHttp::default(), number, part, &answer, &history_path(number), now, &session_token()?)?;
+    match submission.wait {
+        0    => println!("Verdict: {}", submission.verdict),
+        wait => println!("Verdict: {} (next answer in {wait}s)", submission.verdict),
+    }
+    Ok(())
+}
+
+fn create_day(options: &Options) -> Result<(), AocError> {
+    let number = options.days[0];
+    for path in new_day(&workspace_root(), number, options.rectangle)? {
+        println!("wrote {}", path.display());
+    }
+    println!("Day {number} is ready: fill in data/sample.txt and its answers in data/answers.txt");
+    Ok(())
+}
+
+fn report_error(result: Result<(), AocError>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if !options.trace.is_empty() && !trace::ENABLED {
+        eprintln!("Tracing is compiled out, build with `--features trace` to use `--trace`");
+    }
+    trace::set_filters(options.trace.clone());
+
+    match options.command {
+        Command::Run    => run_days(&options),
+        Command::Verify => verify_days(&options),
+        Command::Fetch  => fetch_days(&options),
+        Command::Submit => report_error(submit_answer(&options)),
+        Command::New    => report_error(create_day(&options)),
+    }
+}
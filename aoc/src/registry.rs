@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use parsing::ParseError;
+use solution::{Answer, Fixture, Solution, INPUT_FIXTURE};
+
+
+// ------------ Run class ------------
+/// Wall-clock time spent in each phase of a day
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Answers of both parts, and how long it took to get them
+#[derive(PartialEq, Debug, Clone)]
+pub struct Run {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings
+}
+
+/// Parse the input and solve both parts, timing each phase
+pub fn run<S: Solution>(text: &str, params: &S::Params) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(text)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&input, params);
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&input, params);
+    let part2_time = start.elapsed();
+
+    Ok( Run { part1, part2, timings: Timings { parse, part1: part1_time, part2: part2_time } } )
+}
+
+/// Solve an input with the params of a fixture. Only the parts with an expected answer are run,
+/// as some parts only work on the real input, except for the real input itself.
+pub fn check_fixture<S: Solution>(text: &str, fixture: &Fixture) -> Result<[Option<Answer>; 2], ParseError> {
+    let params = match &fixture.params {
+        Some(text) => S::params(text)?,
+        None       => S::Params::default(),
+    };
+    let input = S::parse(text)?;
+
+    let wanted = |part: usize| fixture.name == INPUT_FIXTURE || fixture.answers[part].is_some();
+    Ok([
+        wanted(0).then(|| S::part1(&input, &params)),
+        wanted(1).then(|| S::part2(&input, &params)),
+    ])
+}
+
+
+// ------------ Day class ------------
+type Solver = fn(&str) -> Result<Run, ParseError>;
+type FixtureSolver = fn(&str, &Fixture) -> Result<[Option<Answer>; 2], ParseError>;
+
+/// Everything the runner needs to know about one day
+pub struct Day {
+    pub number: u32,
+    /// Solves the real input
+    pub solve: Solver,
+    /// Solves a fixture, which may need different params (ie: a smaller grid)
+    pub solve_fixture: FixtureSolver
+}
+
+impl Day {
+    /// Directory of the day crate
+    pub fn dir(&self) -> PathBuf {
+        day_dir(self.number)
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        input_path(self.number)
+    }
+
+    /// Whether the real input was fetched
+    pub fn has_input(&self) -> bool {
+        self.input_path().exists()
+    }
+
+    /// Input of a fixture other than the real input
+    pub fn fixture_path(&self, name: &str) -> PathBuf {
+        self.dir().join(format!("data/{name}.txt"))
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("data/answers.txt")
+    }
+}
+
+/// Root of the workspace, where the day crates live
+pub fn workspace_root() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap().to_path_buf()
+}
+
+/// Directory of a day crate, which may not exist yet
+pub fn day_dir(number: u32) -> PathBuf {
+    workspace_root().join(format!("day{number}"))
+}
+
+/// Where the real input of a day is cached
+pub fn input_path(number: u32) -> PathBuf {
+    day_dir(number).join("data/input.txt")
+}
+
+/// Where the answers submitted for a day are recorded
+pub fn history_path(number: u32) -> PathBuf {
+    day_dir(number).join("data/history.txt")
+}
+
+
+// ------------ Registry ------------
+macro_rules! day {
+    ($number:expr, $solution:ty) => {
+        Day {
+            number: $number,
+            solve: |text| run::<$solution>(text, &Default::default()),
+            solve_fixture: check_fixture::<$solution>,
+        }
+    };
+}
+
+/// Every solved day, in order
+pub fn days() -> Vec<Day> {
+    vec![
+        day!(1, day1::Day1),
+        day!(2, day2::Day2),
+        day!(3, day3::Day3),
+        day!(4, day4::Day4),
+        day!(5, day5::Day5),
+        day!(6, day6::Day6),
+        day!(7, day7::Day7),
+        day!(8, day8::Day8),
+        day!(9, day9::Day9),
+        day!(10, day10::Day10),
+        day!(11, day11::Day11),
+        day!(12, day12::Day12),
+        day!(13, day13::Day13),
+        day!(14, day14::Day14),
+        day!(15, day15::Day15),
+        day!(16, day16::Day16),
+        day!(17, day17::Day17),
+        day!(18, day18::Day18),
+        day!(19, day19::Day19),
+        day!(20, day20::Day20),
+    ]
+}
+
+/// Find a day by its number
+pub fn day(number: u32) -> Option<Day> {
+    days().into_iter().find(|day| day.number == number)
+}
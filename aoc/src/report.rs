@@ -0,0 +1,204 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::registry::Run;
+
+
+// ------------ Report class ------------
+/// Timings of several days, to be saved and compared across commits
+#[derive(PartialEq, Debug, Default)]
+pub struct Report {
+    /// Commit the binary was run on, if known
+    pub commit: Option<String>,
+    pub days: Vec<(u32, Run)>
+}
+
+/// Format of a saved report, chosen from the file extension
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ReportFormat {
+    Json,
+    Csv
+}
+
+/// How `run` prints the answers
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum OutputFormat {
+    Text,
+    Json
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        match name {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            _      => None,
+        }
+    }
+}
+
+impl ReportFormat {
+    pub fn from_path(path: &Path) -> Option<ReportFormat> {
+        match path.extension()?.to_str()? {
+            "json" => Some(ReportFormat::Json),
+            "csv"  => Some(ReportFormat::Csv),
+            _      => None,
+        }
+    }
+}
+
+/// Current git commit, if the workspace is a git repository
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(crate::registry::workspace_root())
+        .output()
+        .ok()?;
+
+    match output.status.success() {
+        true  => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => None,
+    }
+}
+
+fn micros(duration: Duration) -> u128 {
+    duration.as_micros()
+}
+
+/// Escape a string to be put between double quotes in JSON
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"'  => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c    => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Quote a CSV field if it contains separators or quotes
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n']) {
+        true  => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
+
+/// One line of JSON with the answers of a day, their types and the timings in microseconds
+pub fn json_line(day: u32, run: &Run) -> String {
+    let t = &run.timings;
+    let parts = [(1, &run.part1, t.part1), (2, &run.part2, t.part2)].iter()
+        .map(|(part, answer, time)| format!("{{\"part\": {part}, \"answer\": {}, \"type\": \"{}\", \"time_us\": {}}}",
+            json_string(&answer.to_string()), answer.kind(), micros(*time)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{{\"day\": {day}, \"parse_us\": {}, \"total_us\": {}, \"parts\": [{parts}]}}", micros(t.parse), micros(t.total()))
+}
+
+impl Report {
+    /// One row per day, with times in microseconds
+    pub fn to_csv(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("");
+        let mut csv = String::from("commit,day,parse_us,part1_us,part2_us,total_us,part1,part2\n");
+
+        for (day, run) in &self.days {
+            let t = &run.timings;
+            writeln!(csv, "{},{day},{},{},{},{},{},{}",
+                csv_field(commit), micros(t.parse), micros(t.part1), micros(t.part2), micros(t.total()),
+                csv_field(&run.part1.to_string()), csv_field(&run.part2.to_string())).unwrap();
+        }
+        csv
+    }
+
+    /// Same data as the CSV, with times in microseconds
+    pub fn to_json(&self) -> String {
+        let commit = match &self.commit {
+            Some(commit) => json_string(commit),
+            None         => "null".to_string(),
+        };
+
+        let days = self.days.iter()
+            .map(|(day, run)| {
+                let t = &run.timings;
+                format!("    {{\"day\": {day}, \"parse_us\": {}, \"part1_us\": {}, \"part2_us\": {}, \"total_us\": {}, \"part1\": {}, \"part2\": {}}}",
+                    micros(t.parse), micros(t.part1), micros(t.part2), micros(t.total()),
+                    json_string(&run.part1.to_string()), json_string(&run.part2.to_string()))
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+
+        format!("{{\n  \"commit\": {commit},\n  \"days\": [\n{days}\n  ]\n}}\n")
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv  => self.to_csv(),
+        }
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::registry::Timings;
+    use solution::Answer;
+
+    fn report() -> Report {
+        let timings = Timings {
+            parse: Duration::from_micros(5),
+            part1: Duration::from_micros(10),
+            part2: Duration::from_millis(2)
+        };
+        Report {
+            commit: Some("abc123".to_string()),
+            days: vec![(17, Run { part1: "4,6,3".into(), part2: Answer::Unsigned(117440), timings })]
+        }
+    }
+
+    #[test]
+    fn test_report_format() {
+        assert_eq!(ReportFormat::from_path(Path::new("times.json")), Some(ReportFormat::Json));
+        assert_eq!(ReportFormat::from_path(Path::new("out/times.csv")), Some(ReportFormat::Csv));
+        assert_eq!(ReportFormat::from_path(Path::new("times.txt")), None);
+        assert_eq!(ReportFormat::from_path(Path::new("times")), None);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(report().to_csv(),
+            "commit,day,parse_us,part1_us,part2_us,total_us,part1,part2\n\
+             abc123,17,5,10,2000,2015,\"4,6,3\",117440\n");
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(report().to_json(),
+            "{\n  \"commit\": \"abc123\",\n  \"days\": [\n    \
+             {\"day\": 17, \"parse_us\": 5, \"part1_us\": 10, \"part2_us\": 2000, \"total_us\": 2015, \"part1\": \"4,6,3\", \"part2\": \"117440\"}\n  \
+             ]\n}\n");
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+
+    #[test]
+    fn test_json_line() {
+        let (day, run) = &report().days[0];
+        assert_eq!(json_line(*day, run),
+            "{\"day\": 17, \"parse_us\": 5, \"total_us\": 2015, \"parts\": [\
+             {\"part\": 1, \"answer\": \"4,6,3\", \"type\": \"text\", \"time_us\": 10}, \
+             {\"part\": 2, \"answer\": \"117440\", \"type\": \"unsigned\", \"time_us\": 2000}]}");
+    }
+}
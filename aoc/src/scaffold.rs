@@ -0,0 +1,362 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{read, AocError};
+
+
+// ------------ Templates ------------
+// `{n}` is replaced by the day number
+
+static CARGO_TOML: &str = r#"[package]
+name = "day{n}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+parsing = { version = "0.1.0", path = "../parsing" }
+{rectangle}solution = { version = "0.1.0", path = "../solution" }
+
+[dev-dependencies]
+criterion = { version = "0.5.1", features = ["html_reports"] }
+
+[[bench]]
+name = "benchmarks"
+harness = false
+"#;
+
+static GITIGNORE: &str = "/target\n.vscode\n";
+
+static MAIN_RS: &str = r#"use std::fs;
+use std::process;
+
+use day{n}::*;
+
+static INPUT_FILE_PATH: &str = "data/input.txt";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+
+#[allow(dead_code)]
+enum InputType{
+    Sample,
+    File
+}
+
+fn main() {
+    // Input choice --> CHANGE THIS ENUM HERE <--
+    let input_type = InputType::File;
+
+    let input = match input_type {
+        InputType::Sample => SAMPLE_INPUT.to_string(),
+        InputType::File   => fs::read_to_string(INPUT_FILE_PATH)
+                                .expect("Should have been able to read the file")
+    };
+
+    let parsed = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&parsed);
+    println!("Part 1, solution: {part1_solution}");
+
+    let part2_solution = part2(&parsed);
+    println!("Part 2, solution: {part2_solution}");
+}
+"#;
+
+static LIB_RS_LINES: &str = r#"use parsing::ParseError;
+use solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError>
+{
+    Ok( input.lines().map(str::to_string).collect() )
+}
+
+pub fn part1(_lines: &[String]) -> usize
+{
+    0
+}
+
+pub fn part2(_lines: &[String]) -> usize
+{
+    0
+}
+
+pub struct Day{n};
+
+impl Solution for Day{n} {
+    type Input = Vec<String>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+"#;
+
+static LIB_RS_RECTANGLE: &str = r#"use rectangle::Rectangle;
+
+use parsing::{char_grid, ParseError};
+use solution::{Answer, Solution};
+
+pub fn parse(input: &str) -> Result<Rectangle<char>, ParseError>
+{
+    Ok( Rectangle::from_vec(char_grid(input, |c| !c.is_whitespace(), "a tile")?)? )
+}
+
+pub fn part1(_grid: &Rectangle<char>) -> usize
+{
+    0
+}
+
+pub fn part2(_grid: &Rectangle<char>) -> usize
+{
+    0
+}
+
+pub struct Day{n};
+
+impl Solution for Day{n} {
+    type Input = Rectangle<char>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+"#;
+
+static LIB_RS_TESTS: &str = r#"
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    // Checks data/sample.txt against data/answers.txt
+    solution::fixture_tests!(Day{n}: sample);
+}
+"#;
+
+// Left without answers, so the fixture test fails with "No answers" until the ones of the puzzle text
+// are filled in (ie: `sample part1: 143`)
+static ANSWERS_TXT: &str = "";
+
+static BENCHMARKS_RS: &str = r#"use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day{n}::*;
+
+fn criterion_benchmark(c: &mut Criterion) {
+
+    // Fall back to the sample until the input is fetched
+    let input = fs::read_to_string("data/input.txt")
+        .unwrap_or_else(|_| include_str!("../data/sample.txt").to_string());
+    let parsed = parse(&input).unwrap();
+
+    let mut group = c.benchmark_group("day{n}");
+
+    group.bench_function("parse",
+        |b| b.iter(|| parse(black_box(&input))));
+
+    group.bench_function("part1",
+        |b| b.iter(|| part1(black_box(&parsed))));
+
+    group.bench_function("part2",
+        |b| b.iter(|| part2(black_box(&parsed))));
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
+"#;
+
+static RECTANGLE_DEPENDENCY: &str = "rectangle = { version = \"0.1.0\", path = \"../rectangle\" }\n";
+
+/// Files of a new day crate, relative to its directory
+pub fn day_files(number: u32, rectangle: bool) -> Vec<(&'static str, String)> {
+    let fill = |template: &str| template.replace("{n}", &number.to_string());
+
+    let cargo_toml = CARGO_TOML.replace("{rectangle}", if rectangle { RECTANGLE_DEPENDENCY } else { "" });
+    let lib_rs = if rectangle { LIB_RS_RECTANGLE } else { LIB_RS_LINES }.to_string() + LIB_RS_TESTS;
+
+    vec![
+        ("Cargo.toml",              fill(&cargo_toml)),
+        (".gitignore",              GITIGNORE.to_string()),
+        ("src/main.rs",             fill(MAIN_RS)),
+        ("src/lib.rs",              fill(&lib_rs)),
+        ("benches/benchmarks.rs",   fill(BENCHMARKS_RS)),
+        ("data/sample.txt",         String::new()),
+        ("data/answers.txt",        ANSWERS_TXT.to_string()),
+    ]
+}
+
+
+// ------------ Wiring ------------
+/// Insert `line` after the last line starting with `prefix` (ignoring indentation)
+fn insert_after_last(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let position = lines.iter().rposition(|l| l.trim_start().starts_with(prefix))?;
+    lines.insert(position + 1, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Insert `line` after the last line starting with `prefix` inside the `table` of a manifest (ie: `[dependencies]`)
+fn insert_in_table(toml: &str, table: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut lines = toml.lines().collect::<Vec<_>>();
+    let start = lines.iter().position(|l| l.trim() == table)? + 1;
+    let end = lines[start..].iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |len| start + len);
+    let position = lines[start..end].iter().rposition(|l| l.trim_start().starts_with(prefix))?;
+    lines.insert(start + position + 1, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Add the day to the `members` of the workspace manifest, 5 days per row
+pub fn add_workspace_member(cargo_toml: &str, number: u32) -> Option<String> {
+    let member = format!("\"day{number}\",");
+    let mut lines = cargo_toml.lines().map(str::to_string).collect::<Vec<_>>();
+    let position = lines.iter().rposition(|l| l.trim_start().starts_with("\"day"))?;
+
+    if lines[position].matches("\"day").count() < 5 {
+        lines[position] = format!("{} {member}", lines[position]);
+    }
+    else {
+        lines.insert(position + 1, format!("    {member}"));
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// Add the day to the runner: dependency, registry and benchmarks
+pub fn add_to_runner(aoc_toml: &str, registry_rs: &str, benches_rs: &str, number: u32) -> Option<(String, String, String)> {
+    Some((
+        insert_in_table(aoc_toml, "[dependencies]", "day",
+            &format!("day{number} = {{ version = \"0.1.0\", path = \"../day{number}\" }}"))?,
+        insert_after_last(registry_rs, "day!(",
+            &format!("        day!({number}, day{number}::Day{number}),"))?,
+        insert_after_last(benches_rs, "bench_day::<",
+            &format!("    bench_day::<day{number}::Day{number}>(c, {number});"))?,
+    ))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), AocError> {
+    let io_error = |source| AocError::Io { path: path.to_path_buf(), source };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, content).map_err(io_error)
+}
+
+/// Create the crate of a day in the workspace at `root`, and wire it into the workspace and the runner.
+/// Files that already exist (ie: a fetched input) are kept. Returns the files written.
+pub fn new_day(root: &Path, number: u32, rectangle: bool) -> Result<Vec<PathBuf>, AocError> {
+    if !(1..=25).contains(&number) {
+        return Err(AocError::UnknownDay(number));
+    }
+    let day_dir = root.join(format!("day{number}"));
+    if day_dir.join("Cargo.toml").exists() {
+        return Err(AocError::DayExists(number));
+    }
+
+    // Read and update everything before writing, so a failure leaves the workspace untouched
+    let manifest_path = root.join("Cargo.toml");
+    let aoc_toml_path = root.join("aoc/Cargo.toml");
+    let registry_path = root.join("aoc/src/registry.rs");
+    let benches_path = root.join("aoc/benches/days.rs");
+
+    let cannot_wire = |path: &Path| AocError::Scaffold(format!("cannot find where to add day{number} in {}", path.display()));
+    let manifest = add_workspace_member(&read(&manifest_path)?, number)
+        .ok_or_else(|| cannot_wire(&manifest_path))?;
+    let (aoc_toml, registry, benches) = add_to_runner(&read(&aoc_toml_path)?, &read(&registry_path)?, &read(&benches_path)?, number)
+        .ok_or_else(|| cannot_wire(&root.join("aoc")))?;
+
+    let mut written = Vec::new();
+    for (file, content) in day_files(number, rectangle) {
+        let path = day_dir.join(file);
+        if !path.exists() {
+            write(&path, &content)?;
+            written.push(path);
+        }
+    }
+
+    for (path, content) in [
+        (manifest_path, manifest),
+        (aoc_toml_path, aoc_toml),
+        (registry_path, registry),
+        (benches_path, benches),
+    ] {
+        write(&path, &content)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::env;
+
+    static MANIFEST: &str = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\", \"day2\", \"day3\", \"day4\", \"day5\",\n    \"day6\",\n]\n";
+
+    #[test]
+    fn test_add_workspace_member() {
+        let manifest = add_workspace_member(MANIFEST, 7).unwrap();
+        assert!(manifest.contains("    \"day6\", \"day7\",\n]"));
+
+        let full = MANIFEST.replace("\"day6\",", "\"day6\", \"day7\", \"day8\", \"day9\", \"day10\",");
+        assert!(add_workspace_member(&full, 11).unwrap().contains("\"day10\",\n    \"day11\",\n]"));
+        assert_eq!(add_workspace_member("[workspace]\n", 1), None);
+    }
+
+    #[test]
+    fn test_day_files() {
+        let files = day_files(21, true);
+        let file = |name: &str| &files.iter().find(|(file, _)| *file == name).unwrap().1;
+        assert!(file("Cargo.toml").contains("name = \"day21\""));
+        assert!(file("Cargo.toml").contains("rectangle = "));
+        assert!(file("src/lib.rs").contains("pub struct Day21;"));
+        assert!(file("src/lib.rs").contains("solution::fixture_tests!(Day21: sample);"));
+        assert!(file("src/main.rs").contains("use day21::*;"));
+        assert!(file("benches/benchmarks.rs").contains("benchmark_group(\"day21\")"));
+
+        assert_eq!(file("data/answers.txt"), "");
+
+        let files = day_files(21, false);
+        assert!(!files[0].1.contains("rectangle"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root.join("Cargo.toml"), MANIFEST).unwrap();
+        let aoc_toml = "[dependencies]\nday6 = { version = \"0.1.0\", path = \"../day6\" }\n\n\
+                        [dev-dependencies]\nday6 = { version = \"0.1.0\", path = \"../day6\", features = [\"generator\"] }\n";
+        write(&root.join("aoc/Cargo.toml"), aoc_toml).unwrap();
+        write(&root.join("aoc/src/registry.rs"), "    vec![\n        day!(6, day6::Day6),\n    ]\n").unwrap();
+        write(&root.join("aoc/benches/days.rs"), "    bench_day::<day6::Day6>(c, 6);\n}\n").unwrap();
+        // A fetched input is kept
+        write(&root.join("day7/data/input.txt"), "real input").unwrap();
+
+        let written = new_day(&root, 7, false).unwrap();
+        assert_eq!(written.len(), 11);
+        assert_eq!(fs::read_to_string(root.join("day7/data/input.txt")).unwrap(), "real input");
+        assert!(fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap().contains("day!(6, day6::Day6),\n        day!(7, day7::Day7),\n"));
+        assert!(fs::read_to_string(root.join("aoc/benches/days.rs")).unwrap().contains("bench_day::<day7::Day7>(c, 7);"));
+        assert!(fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap().starts_with(
+            "[dependencies]\nday6 = { version = \"0.1.0\", path = \"../day6\" }\nday7 = { version = \"0.1.0\", path = \"../day7\" }\n\n[dev-dependencies]\n"));
+
+        assert!(matches!(new_day(&root, 7, false), Err(AocError::DayExists(7))));
+        assert!(matches!(new_day(&root, 26, false), Err(AocError::UnknownDay(26))));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
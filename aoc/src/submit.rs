@@ -0,0 +1,349 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use parsing::{parse_number, space_list, ParseError};
+
+use crate::fetch::{first_line, YEAR};
+use crate::http::{Http, BASE_URL};
+use crate::{read, AocError};
+
+/// Wait after a wrong answer when the site does not say how long
+static DEFAULT_COOLDOWN: u64 = 60;
+
+
+// ------------ Verdict class ------------
+/// What the site said about a submitted answer
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Submitted too early after the previous answer
+    RateLimited,
+    /// The part cannot be answered now: it is already solved, or it is part 2 and part 1 is not
+    WrongLevel
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verdict::Correct     => "correct",
+            Verdict::Incorrect   => "incorrect",
+            Verdict::TooHigh     => "too-high",
+            Verdict::TooLow      => "too-low",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::WrongLevel  => "wrong-level",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "correct"      => Ok(Verdict::Correct),
+            "incorrect"    => Ok(Verdict::Incorrect),
+            "too-high"     => Ok(Verdict::TooHigh),
+            "too-low"      => Ok(Verdict::TooLow),
+            "rate-limited" => Ok(Verdict::RateLimited),
+            "wrong-level"  => Ok(Verdict::WrongLevel),
+            _              => Err(()),
+        }
+    }
+}
+
+/// Parse a duration like `1m 5s` or `37s`
+fn parse_wait(text: &str) -> Option<u64> {
+    text.split_whitespace()
+        .map(|token| {
+            let (number, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "s" => Some(number),
+                "m" => Some(number * 60),
+                "h" => Some(number * 3600),
+                _   => None,
+            }
+        })
+        .sum()
+}
+
+/// Read the verdict, and how many seconds to wait before the next answer, from the response page
+pub fn parse_verdict(body: &str) -> Option<(Verdict, u64)> {
+    // Wrong answers say `Please wait one minute` (or `5 minutes` after several attempts)
+    let wrong_answer_wait = || {
+        let lowercase = body.to_ascii_lowercase();
+        let after = &lowercase[lowercase.find("please wait ")? + "please wait ".len()..];
+        let amount = after.split_whitespace().next()?;
+        let minutes = match amount {
+            "one" => 1,
+            n     => n.parse::<u64>().ok()?,
+        };
+        Some(minutes * 60)
+    };
+
+    if body.contains("That's the right answer") {
+        Some( (Verdict::Correct, 0) )
+    }
+    else if body.contains("You gave an answer too recently") {
+        // ie: `You have 1m 5s left to wait.`
+        let wait = body.find("You have ")
+            .and_then(|start| {
+                let after = &body[start + "You have ".len()..];
+                parse_wait(&after[..after.find(" left to wait")?])
+            })
+            .unwrap_or(DEFAULT_COOLDOWN);
+        Some( (Verdict::RateLimited, wait) )
+    }
+    else if body.contains("That's not the right answer") {
+        let verdict = if body.contains("your answer is too high") { Verdict::TooHigh }
+            else if body.contains("your answer is too low") { Verdict::TooLow }
+            else { Verdict::Incorrect };
+        Some( (verdict, wrong_answer_wait().unwrap_or(DEFAULT_COOLDOWN)) )
+    }
+    else if body.contains("You don't seem to be solving the right level") {
+        Some( (Verdict::WrongLevel, 0) )
+    }
+    else {
+        None
+    }
+}
+
+
+// ------------ History class ------------
+/// One submitted answer, stored as a line `<unix time> part<1|2> <answer> <verdict> <wait>`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Submission {
+    pub time: u64,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds to wait before submitting again
+    pub wait: u64
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part{} {} {} {}", self.time, self.part, self.answer, self.verdict, self.wait)
+    }
+}
+
+/// Every answer submitted for a day, oldest first
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct History {
+    pub submissions: Vec<Submission>
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History, ParseError> {
+        let expected = "`<time> part<1|2> <answer> <verdict> <wait>`";
+
+        let submissions = text.lines()
+            .filter(|line| !line.trim().is_empty() )
+            .map(|line| {
+                let [time, part, answer, verdict, wait]: [&str; 5] = space_list(line).collect::<Vec<_>>()
+                    .try_into()
+                    .map_err(|_| ParseError::unexpected(text, line, expected))?;
+                let part = match part {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _       => return Err(ParseError::unexpected(text, part, "`part1` or `part2`")),
+                };
+                Ok( Submission {
+                    time: parse_number(text, time, "a unix time")?,
+                    part,
+                    answer: answer.to_string(),
+                    verdict: verdict.parse().map_err(|_| ParseError::unexpected(text, verdict, "a verdict"))?,
+                    wait: parse_number(text, wait, "a number of seconds")?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok( History { submissions } )
+    }
+
+    /// Load the history of a day, which is empty if nothing was submitted yet
+    pub fn load(path: &Path) -> Result<History, AocError> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        History::parse(&read(path)?)
+            .map_err(|source| AocError::Parse { path: path.to_path_buf(), source })
+    }
+
+    /// Append a submission to the history file
+    pub fn append(path: &Path, submission: &Submission) -> Result<(), AocError> {
+        let io_error = |source| AocError::Io { path: path.to_path_buf(), source };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(io_error)?;
+        writeln!(file, "{submission}").map_err(io_error)
+    }
+
+    /// Reason not to submit `answer` for `part` at time `now`, based on the previous answers
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        // Cooldown of the last submission, whatever the part
+        if let Some(last) = self.submissions.last() {
+            let ready = last.time + last.wait;
+            if now < ready {
+                return Err(format!("wait {}s before submitting again", ready - now));
+            }
+        }
+
+        let number = answer.parse::<i64>().ok();
+        for previous in self.submissions.iter().filter(|s| s.part == part) {
+            let previous_number = previous.answer.parse::<i64>().ok();
+            match previous.verdict {
+                Verdict::Correct =>
+                    return Err(format!("part {part} is already solved")),
+                // Nothing was learned about these answers, they can be sent again
+                Verdict::RateLimited | Verdict::WrongLevel => (),
+                _ if previous.answer == answer =>
+                    return Err(format!("`{answer}` was already {}", previous.verdict)),
+                Verdict::TooHigh if matches!((number, previous_number), (Some(n), Some(high)) if n >= high) =>
+                    return Err(format!("`{answer}` is too high, `{}` already was", previous.answer)),
+                Verdict::TooLow if matches!((number, previous_number), (Some(n), Some(low)) if n <= low) =>
+                    return Err(format!("`{answer}` is too low, `{}` already was", previous.answer)),
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+// ------------ Submit ------------
+pub fn answer_url(number: u32) -> String {
+    format!("{BASE_URL}/{YEAR}/day/{number}/answer")
+}
+
+/// Submit the answer of a part, unless the history says it would be pointless or too early.
+/// Every answer actually sent is appended to the history file.
+pub fn submit(http: &dyn Http, number: u32, part: u8, answer: &str, history_path: &Path, now: u64, session: &str)
+    -> Result<Submission, AocError>
+{
+    History::load(history_path)?
+        .check(part, answer, now)
+        .map_err(AocError::Refused)?;
+
+    let url = answer_url(number);
+    let level = part.to_string();
+    let response = http.post_form(&url, session, &[("level", &level), ("answer", answer)])?;
+
+    let (verdict, wait) = match (response.status, parse_verdict(&response.body)) {
+        (200, Some(verdict)) => verdict,
+        (status, _)          => return Err(AocError::Http { url, status, message: first_line(&response.body) }),
+    };
+
+    let submission = Submission { time: now, part, answer: answer.to_string(), verdict, wait };
+    History::append(history_path, &submission)?;
+    Ok(submission)
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::http::fake::{FakeHttp, TempDir};
+
+    static CORRECT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article>";
+    static TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+    static TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p></article>";
+    static INCORRECT: &str = "<article><p>That's not the right answer.  If you're stuck, please wait one minute before trying again.</p></article>";
+    static TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+    static WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>";
+
+    fn submission(time: u64, part: u8, answer: &str, verdict: Verdict, wait: u64) -> Submission {
+        Submission { time, part, answer: answer.to_string(), verdict, wait }
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(parse_verdict(CORRECT), Some((Verdict::Correct, 0)));
+        assert_eq!(parse_verdict(TOO_HIGH), Some((Verdict::TooHigh, 60)));
+        assert_eq!(parse_verdict(TOO_LOW), Some((Verdict::TooLow, 300)));
+        assert_eq!(parse_verdict(INCORRECT), Some((Verdict::Incorrect, 60)));
+        assert_eq!(parse_verdict(TOO_RECENT), Some((Verdict::RateLimited, 65)));
+        assert_eq!(parse_verdict(WRONG_LEVEL), Some((Verdict::WrongLevel, 0)));
+        assert_eq!(parse_verdict("<html>Maintenance</html>"), None);
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let history = History { submissions: vec![
+            submission(100, 1, "42", Verdict::TooHigh, 60),
+            submission(200, 2, "6,1", Verdict::Correct, 0),
+        ]};
+        let text = history.submissions.iter().map(|s| format!("{s}\n")).collect::<String>();
+        assert_eq!(text, "100 part1 42 too-high 60\n200 part2 6,1 correct 0\n");
+        assert_eq!(History::parse(&text), Ok(history));
+
+        assert_eq!(
+            History::parse("100 part3 42 too-high 60"),
+            Err(ParseError::Unexpected(1, 5, "`part1` or `part2`".to_string(), "part3".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_history_check() {
+        let history = History { submissions: vec![
+            submission(1000, 1, "500", Verdict::TooHigh, 60),
+            submission(1100, 1, "100", Verdict::TooLow, 60),
+            submission(1200, 1, "300", Verdict::Incorrect, 60),
+        ]};
+        assert_eq!(history.check(1, "200", 1230), Err("wait 30s before submitting again".to_string()));
+        assert_eq!(history.check(1, "200", 1260), Ok(()));
+        assert_eq!(history.check(1, "600", 1300), Err("`600` is too high, `500` already was".to_string()));
+        assert_eq!(history.check(1, "50", 1300), Err("`50` is too low, `100` already was".to_string()));
+        assert_eq!(history.check(1, "300", 1300), Err("`300` was already incorrect".to_string()));
+        assert_eq!(history.check(2, "600", 1300), Ok(()));
+
+        let solved = History { submissions: vec![submission(0, 1, "7", Verdict::Correct, 0)] };
+        assert_eq!(solved.check(1, "8", 10), Err("part 1 is already solved".to_string()));
+
+        // Part 2 sent before part 1 was solved can be sent again later
+        let early = History { submissions: vec![submission(0, 2, "7", Verdict::WrongLevel, 0)] };
+        assert_eq!(early.check(2, "7", 10), Ok(()));
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = TempDir::new("submit");
+        let path = dir.0.join("data/history.txt");
+        let http = FakeHttp::default().with(&answer_url(1), 200, TOO_HIGH);
+
+        let result = submit(&http, 1, 1, "500", &path, 1000, "abc").unwrap();
+        assert_eq!(result, submission(1000, 1, "500", Verdict::TooHigh, 60));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000 part1 500 too-high 60\n");
+
+        // Refused locally, without contacting the site
+        assert!(matches!(submit(&http, 1, 1, "400", &path, 1010, "abc"), Err(AocError::Refused(_))));
+        assert!(matches!(submit(&http, 1, 1, "501", &path, 2000, "abc"), Err(AocError::Refused(_))));
+        assert_eq!(http.requests.borrow().len(), 1);
+        assert_eq!(http.requests.borrow()[0], format!("POST {} session=abc level=1&answer=500", answer_url(1)));
+
+        let http = FakeHttp::default().with(&answer_url(1), 200, CORRECT);
+        assert_eq!(submit(&http, 1, 1, "400", &path, 2000, "abc").unwrap().verdict, Verdict::Correct);
+        assert_eq!(History::load(&path).unwrap().submissions.len(), 2);
+    }
+
+    #[test]
+    fn test_submit_unknown_response() {
+        let dir = TempDir::new("submit-unknown");
+        let path = dir.0.join("data/history.txt");
+        let http = FakeHttp::default().with(&answer_url(2), 200, "<html>Maintenance</html>");
+        assert!(matches!(submit(&http, 2, 1, "1", &path, 0, "abc"), Err(AocError::Http { status: 200, .. })));
+        assert!(!path.exists());
+    }
+}
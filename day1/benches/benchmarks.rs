@@ -1,17 +1,16 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
 use day1::*;
+use day1::columns::read_columns;
 
-static DAY_1_INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+static DAY_1_INPUT: &str = include_str!("../data/input.txt");
 
 fn criterion_benchmark(c: &mut Criterion) {
 
-    let input= DAY_1_INPUT.to_string();
-    let input_ref = &input;
+    // The naive approach does not handle the trailing new line
+    let input_ref = DAY_1_INPUT.trim_end();
     let mut group = c.benchmark_group("day1_part1");
 
     group.bench_function("naive",
@@ -21,10 +20,37 @@ fn criterion_benchmark(c: &mut Criterion) {
         |b| b.iter(|| day1_part1_no_string_copy(black_box(input_ref))));
 
     group.bench_function("final",
-        |b| b.iter(|| day1_part1(black_box(input_ref))));
+        |b| b.iter(|| day1_part1(&parse(black_box(input_ref)).unwrap())));
+
+    group.bench_function("streaming",
+        |b| b.iter(|| read_columns(black_box(input_ref.as_bytes())).unwrap().total_distance().unwrap()));
+
+    group.finish();
+
+    // Both parts from the text, on generated lists of growing size
+    let mut group = c.benchmark_group("day1_scaling");
+    group.sample_size(10);
+
+    for pairs in [10_000, 100_000, 1_000_000] {
+        let options = generator::Options { pairs, ..Default::default() };
+        let text = generator::generate(&mut ChaCha8Rng::seed_from_u64(1), &options);
+        group.throughput(Throughput::Elements(pairs as u64));
+
+        group.bench_with_input(BenchmarkId::new("hash_maps", pairs), &text,
+            |b, text| b.iter(|| {
+                let pairs = parse(black_box(text)).unwrap();
+                (day1_part1(&pairs), day1_part2(&pairs))
+            }));
+
+        group.bench_with_input(BenchmarkId::new("streaming", pairs), &text,
+            |b, text| b.iter(|| {
+                let columns = read_columns(black_box(text.as_bytes())).unwrap();
+                (columns.total_distance().unwrap(), columns.similarity_score().unwrap())
+            }));
+    }
 
     group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
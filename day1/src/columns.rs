@@ -0,0 +1,90 @@
+use std::io::BufRead;
+
+use parsing::ParseError;
+
+use crate::lists::{read_ids, LocationLists, Metric, Overflow};
+
+
+// ------------ Columns class ------------
+/// Both location lists of the puzzle: `LocationLists` with a left and a right column
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Columns(LocationLists);
+
+impl Columns {
+    pub fn new(left: Vec<i64>, right: Vec<i64>) -> Self {
+        Columns(LocationLists::new(vec![left, right]))
+    }
+
+    pub fn left(&self) -> &[i64] {
+        self.0.column(0)
+    }
+
+    pub fn right(&self) -> &[i64] {
+        self.0.column(1)
+    }
+
+    /// Sum of the distances between the smallest ids of both lists, the second smallest ones...
+    pub fn total_distance(&self) -> Result<u64, Overflow> {
+        self.0.distance(0, 1, Metric::Manhattan)
+    }
+
+    /// Sum of every left id times the number of times it appears on the right list
+    pub fn similarity_score(&self) -> Result<i64, Overflow> {
+        self.0.similarity_score(0, 1)
+    }
+}
+
+/// Read and sort the lists, see `read_pairs`
+pub fn read_columns<R: BufRead>(reader: R) -> Result<Columns, ParseError> {
+    let (left, right) = read_pairs(reader)?;
+    Ok( Columns::new(left, right) )
+}
+
+/// Read the left and right lists, two whitespace separated ids per line
+pub fn read_pairs<R: BufRead>(reader: R) -> Result<(Vec<i64>, Vec<i64>), ParseError> {
+    let mut columns = read_ids(reader, Some(2))?.into_iter();
+    Ok( (columns.next().unwrap_or_default(), columns.next().unwrap_or_default()) )
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+
+    #[test]
+    fn test_read_columns() {
+        let columns = read_columns(SAMPLE_INPUT.as_bytes()).unwrap();
+        assert_eq!(columns.left(), [1, 2, 3, 3, 3, 4]);
+        assert_eq!(columns.right(), [3, 3, 3, 4, 5, 9]);
+        assert_eq!(columns.total_distance(), Ok(11));
+        assert_eq!(columns.similarity_score(), Ok(31));
+
+        assert_eq!(read_columns("1   2\r\n3   4".as_bytes()).unwrap(), Columns::new(vec![1, 3], vec![2, 4]));
+        assert_eq!(
+            read_columns("1   2\n3   x\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 5, "a number".to_string(), "x".to_string())
+        );
+        assert_eq!(
+            read_columns("1   2\n3\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 1, "2 numbers separated by spaces".to_string(), "3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_large_ids() {
+        // Beyond i32, and too far apart to be sorted by counting
+        let columns = Columns::new(vec![-5_000_000_000, 7_000_000_000, 0], vec![7_000_000_000, -1, 7_000_000_000]);
+        assert_eq!(columns.left(), [-5_000_000_000, 0, 7_000_000_000]);
+        assert_eq!(columns.total_distance(), Ok(5_000_000_000 - 1 + 7_000_000_000));
+        assert_eq!(columns.similarity_score(), Ok(14_000_000_000));
+
+        let columns = Columns::new(vec![i64::MIN, i64::MIN], vec![i64::MAX, i64::MAX]);
+        assert_eq!(columns.total_distance(), Err(Overflow));
+        assert_eq!(Columns::new(vec![i64::MIN], vec![i64::MAX]).total_distance(), Ok(u64::MAX));
+    }
+}
@@ -0,0 +1,58 @@
+use rand::Rng;
+
+/// Shape of the generated location lists
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of lines
+    pub pairs: usize,
+    /// Largest location id
+    pub max_id: i32,
+    /// Chance of a right id being copied from the left list, so that it adds to the similarity score
+    pub shared: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { pairs: 1000, max_id: 99999, shared: 0.2 }
+    }
+}
+
+/// Two lists of location ids, one pair per line separated by three spaces
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let left = (0..options.pairs)
+        .map(|_| rng.random_range(0..=options.max_id))
+        .collect::<Vec<_>>();
+
+    let mut output = String::new();
+    for &l in &left {
+        let r = match rng.random_bool(options.shared) {
+            true  => left[rng.random_range(0..left.len())],
+            false => rng.random_range(0..=options.max_id),
+        };
+        output += &format!("{l}   {r}\n");
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { pairs: 50, max_id: 9, shared: 1.0 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(1), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(1), &options));
+
+        let pairs = parse(&input).unwrap();
+        assert_eq!(pairs.len(), 50);
+        assert!(pairs.iter().all(|&(_, r)| pairs.iter().any(|&(l, _)| l == r)));
+    }
+}
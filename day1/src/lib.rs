@@ -1,6 +1,16 @@
 use std::collections::HashMap;
 
-pub fn day1_part1_naive(text: & String) -> i32
+use parsing::{parse_number, ParseError};
+use solution::{Answer, Solution};
+
+pub mod columns;
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod lists;
+
+use columns::{read_columns, Columns};
+
+pub fn day1_part1_naive(text: &str) -> i32
 {
     // Convenience modification
     let modified_text: String = text.replace("   ", " ");
@@ -30,7 +40,7 @@ pub fn day1_part1_naive(text: & String) -> i32
 }
 
 
-pub fn day1_part1_no_string_copy(text: & String) -> i32
+pub fn day1_part1_no_string_copy(text: &str) -> i32
 {
     // Make single list with all numbers
     let numbers = text.lines()
@@ -56,20 +66,20 @@ pub fn day1_part1_no_string_copy(text: & String) -> i32
                     .sum()
 }
 
-pub fn day1_part1(text: & String) -> i32
+pub fn parse(text: &str) -> Result<Vec<(i32, i32)>, ParseError>
 {
     // Get value pairs
-    let pairs = text.lines()
+    text.lines()
         .map( |line| {
-            let mut values = line.split("   ")
-            .map( |val|
-                val.parse::<i32>().expect("Failed to parse")
-            );
-            (values.next().unwrap(), values.next().unwrap())
+            let (left, right) = line.split_once("   ")
+                .ok_or_else(|| ParseError::unexpected(text, line, "two numbers separated by three spaces"))?;
+            Ok( (parse_number(text, left, "a number")?, parse_number(text, right, "a number")?) )
         } )
-        .collect::<Vec<_>>();
-
+        .collect()
+}
 
+pub fn day1_part1(pairs: &[(i32, i32)]) -> i32
+{
     // Transpose the list of tuples into a tuple of lists
     let (mut column1, mut column2): (Vec<i32>, Vec<i32>) = pairs.iter().cloned().unzip();
 
@@ -83,20 +93,8 @@ pub fn day1_part1(text: & String) -> i32
                     .sum()
 }
 
-pub fn day1_part2(text: & String) -> i32
+pub fn day1_part2(pairs: &[(i32, i32)]) -> i32
 {
-    // Get value pairs
-    let pairs = text.lines()
-        .map( |line| {
-            let mut values = line.split("   ")
-            .map( |val|
-                val.parse::<i32>().expect("Failed to parse")
-            );
-            (values.next().unwrap(), values.next().unwrap())
-        } )
-        .collect::<Vec<_>>();
-
-
     // Transpose the list of tuples into a tuple of lists
     let (column1, column2): (Vec<i32>, Vec<i32>) = pairs.iter().cloned().unzip();
 
@@ -121,3 +119,45 @@ pub fn day1_part2(text: & String) -> i32
         .sum()
 }
 
+pub struct Day1;
+
+/// The lists are sorted once while parsing, and both parts share them
+impl Solution for Day1 {
+    type Input = Columns;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { read_columns(input.as_bytes()) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { input.total_distance().into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { input.similarity_score().into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day1: sample);
+
+    proptest::proptest! {
+        // The naive approach does not handle the trailing new line nor an empty input
+        #[test]
+        fn test_part1_variants_agree(pairs in proptest::collection::vec((0..100_000_i32, 0..100_000_i32), 1..50)) {
+            let text = pairs.iter()
+                .map(|(left, right)| format!("{left}   {right}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let pairs = parse(&text).unwrap();
+            let distance = day1_part1(&pairs);
+            proptest::prop_assert_eq!(day1_part1_naive(&text), distance);
+            proptest::prop_assert_eq!(day1_part1_no_string_copy(&text), distance);
+
+            let columns = read_columns(text.as_bytes()).unwrap();
+            proptest::prop_assert_eq!(columns.total_distance(), Ok(distance as u64));
+            proptest::prop_assert_eq!(columns.similarity_score(), Ok(day1_part2(&pairs) as i64));
+        }
+    }
+}
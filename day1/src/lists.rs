@@ -0,0 +1,321 @@
+use std::io::BufRead;
+
+use thiserror::Error;
+
+use parsing::{parse_number, space_list, ParseError};
+
+/// Counts allocated per id, at most, to sort by counting. Sparser ids are sorted by radix.
+const COUNTS_PER_ID: u64 = 4;
+
+/// A statistic of the lists beyond 64 bits (ie: the distance between ids close to `i64::MIN` and `i64::MAX`)
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy)]
+#[error("The result does not fit in 64 bits")]
+pub struct Overflow;
+
+/// How far apart two columns are, once both are sorted and paired in order
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Metric {
+    /// Sum of the absolute differences, as in the puzzle
+    Manhattan,
+    /// Sum of the squared differences
+    SquaredEuclidean,
+    /// Largest absolute difference
+    Chebyshev,
+    /// Number of pairs with different ids
+    Mismatches
+}
+
+/// A value found in two columns, and how many times it appears in each one
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Shared {
+    pub value: i64,
+    pub counts: (usize, usize)
+}
+
+
+// ------------ LocationLists class ------------
+/// Any number of location lists, each one sorted. Columns are given by index, and methods panic
+/// on columns that do not exist, like slices do.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct LocationLists {
+    columns: Vec<Vec<i64>>
+}
+
+impl LocationLists {
+    pub fn new(mut columns: Vec<Vec<i64>>) -> Self {
+        for column in columns.iter_mut() {
+            sort_ids(column);
+        }
+        LocationLists { columns }
+    }
+
+    /// Number of columns
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn column(&self, idx: usize) -> &[i64] {
+        &self.columns[idx]
+    }
+
+    /// Distance between two columns, pairing their smallest ids, their second smallest ones...
+    pub fn distance(&self, a: usize, b: usize, metric: Metric) -> Result<u64, Overflow> {
+        let mut differences = self.columns[a].iter().zip(&self.columns[b])
+            .map( |(x, y)| x.abs_diff(*y) );
+
+        match metric {
+            Metric::Manhattan        => differences.try_fold(0_u64, |total, d| total.checked_add(d)).ok_or(Overflow),
+            Metric::SquaredEuclidean => differences.try_fold(0_u64, |total, d| total.checked_add(d.checked_mul(d)?)).ok_or(Overflow),
+            Metric::Chebyshev        => Ok( differences.max().unwrap_or(0) ),
+            Metric::Mismatches       => Ok( differences.filter(|&d| d != 0).count() as u64 ),
+        }
+    }
+
+    /// Sum of every value of column `a` times the number of times it appears in column `b`
+    pub fn similarity_score(&self, a: usize, b: usize) -> Result<i64, Overflow> {
+        similarity_score(&self.columns[a], &self.columns[b])
+    }
+
+    /// Every distinct value of a column and how many times it appears, in increasing order
+    pub fn histogram(&self, idx: usize) -> Vec<(i64, usize)> {
+        self.columns[idx].chunk_by(|x, y| x == y)
+            .map( |run| (run[0], run.len()) )
+            .collect()
+    }
+
+    /// The `k` values found in both columns that appear the most times overall. Ties go to the smallest value.
+    pub fn top_shared(&self, a: usize, b: usize, k: usize) -> Vec<Shared> {
+        let mut shared = shared(&self.columns[a], &self.columns[b]);
+        shared.sort_by_key(|s| (std::cmp::Reverse(s.counts.0 + s.counts.1), s.value));
+        shared.truncate(k);
+        shared
+    }
+}
+
+/// Read and sort whitespace separated columns, see `read_ids`
+pub fn read_lists<R: BufRead>(reader: R) -> Result<LocationLists, ParseError> {
+    Ok( LocationLists::new(read_ids(reader, None)?) )
+}
+
+/// Read whitespace separated columns one line at a time, reusing the same line buffer. Every line must have
+/// `width` ids, or as many ids as the first one when not given.
+pub fn read_ids<R: BufRead>(mut reader: R, width: Option<usize>) -> Result<Vec<Vec<i64>>, ParseError> {
+    let mut columns: Vec<Vec<i64>> = vec![Vec::new(); width.unwrap_or(0)];
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)
+            .map_err(|error| ParseError::Invalid(format!("Cannot read the location lists: {error}")))?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        let ids = space_list(text)
+            .map( |id| parse_number(text, id, "a number") )
+            .collect::<Result<Vec<i64>, _>>()
+            .map_err(|error| error.at_line(line_number))?;
+
+        if line_number == 1 && width.is_none() {
+            columns = vec![Vec::new(); ids.len()];
+        }
+        if ids.len() != columns.len() || ids.is_empty() {
+            let expected = match columns.len() {
+                0 => "at least one number".to_string(),
+                1 => "a single number".to_string(),
+                n => format!("{n} numbers separated by spaces"),
+            };
+            return Err(ParseError::unexpected(text, text, &expected).at_line(line_number));
+        }
+
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.push(id);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Values found in both sorted columns, in increasing order. Both are walked at once, in linear time.
+pub(crate) fn shared(a: &[i64], b: &[i64]) -> Vec<Shared> {
+    let mut runs_b = b.chunk_by(|x, y| x == y).peekable();
+
+    a.chunk_by(|x, y| x == y)
+        .filter_map( |run_a| {
+            while runs_b.next_if(|run_b| run_b[0] < run_a[0]).is_some() {}
+            runs_b.next_if(|run_b| run_b[0] == run_a[0])
+                .map( |run_b| Shared { value: run_a[0], counts: (run_a.len(), run_b.len()) } )
+        })
+        .collect()
+}
+
+/// See `LocationLists::similarity_score`, for sorted columns
+pub(crate) fn similarity_score(a: &[i64], b: &[i64]) -> Result<i64, Overflow> {
+    shared(a, b).iter()
+        .try_fold(0_i64, |total, s| {
+            let count = i64::try_from(s.counts.0.checked_mul(s.counts.1)?).ok()?;
+            total.checked_add(s.value.checked_mul(count)?)
+        })
+        .ok_or(Overflow)
+}
+
+
+// ------------ Sorting ------------
+/// Sort in linear time: by counting when there are many ids close together (ie: a million 5-digit ids), by radix otherwise
+pub fn sort_ids(ids: &mut [i64]) {
+    let (Some(&min), Some(&max)) = (ids.iter().min(), ids.iter().max()) else {
+        return;
+    };
+
+    let spread = max.abs_diff(min);
+    match spread < COUNTS_PER_ID.saturating_mul(ids.len() as u64) {
+        true  => counting_sort(ids, min, spread as usize + 1),
+        false => radix_sort(ids),
+    }
+}
+
+fn counting_sort(ids: &mut [i64], min: i64, range: usize) {
+    let mut counts = vec![0; range];
+    for &id in ids.iter() {
+        counts[id.abs_diff(min) as usize] += 1;
+    }
+
+    let mut start = 0;
+    for (offset, &count) in counts.iter().enumerate() {
+        ids[start..start + count].fill(min + offset as i64);
+        start += count;
+    }
+}
+
+/// Least significant digit first, one byte at a time
+fn radix_sort(ids: &mut [i64]) {
+    // Flipping the sign bit puts negative ids first when compared as unsigned
+    let key = |id: i64| (id as u64) ^ (1 << 63);
+    let mut buffer = vec![0; ids.len()];
+
+    for shift in (0..64).step_by(8) {
+        let digit = |id: i64| ((key(id) >> shift) & 0xff) as usize;
+
+        // Count each digit, one position to the right, so that the prefix sums are the start of each bucket
+        let mut starts = [0; 257];
+        for &id in ids.iter() {
+            starts[digit(id) + 1] += 1;
+        }
+
+        // Skip the bytes that are the same for every id (ie: the high bytes of small ids)
+        if starts.contains(&ids.len()) {
+            continue;
+        }
+
+        for idx in 1..starts.len() {
+            starts[idx] += starts[idx - 1];
+        }
+        for &id in ids.iter() {
+            buffer[starts[digit(id)]] = id;
+            starts[digit(id)] += 1;
+        }
+        ids.copy_from_slice(&buffer);
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+
+    #[test]
+    fn test_read_lists() {
+        let lists = read_lists(SAMPLE_INPUT.as_bytes()).unwrap();
+        assert_eq!(lists.len(), 2);
+        assert_eq!(lists.distance(0, 1, Metric::Manhattan), Ok(11));
+        assert_eq!(lists.similarity_score(0, 1), Ok(31));
+
+        let lists = read_lists("3 1\t2\n 1  1 5 \n2 4 2\n".as_bytes()).unwrap();
+        assert_eq!(lists.len(), 3);
+        assert_eq!(lists.column(2), [2, 2, 5]);
+
+        assert_eq!(
+            read_lists("1 2 3\n4 5\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 1, "3 numbers separated by spaces".to_string(), "4 5".to_string())
+        );
+        assert_eq!(
+            read_lists("1 2\n\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 1, "2 numbers separated by spaces".to_string(), "".to_string())
+        );
+        assert_eq!(
+            read_lists("\n1 2\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(1, 1, "at least one number".to_string(), "".to_string())
+        );
+        assert_eq!(
+            read_ids("1 2 3\n".as_bytes(), Some(2)).unwrap_err(),
+            ParseError::Unexpected(1, 1, "2 numbers separated by spaces".to_string(), "1 2 3".to_string())
+        );
+        assert_eq!(read_ids("".as_bytes(), Some(2)), Ok(vec![vec![], vec![]]));
+        assert_eq!(
+            read_lists("1\n2 3\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 1, "a single number".to_string(), "2 3".to_string())
+        );
+        assert_eq!(
+            read_lists("1 2\n3 -x\n".as_bytes()).unwrap_err(),
+            ParseError::Unexpected(2, 3, "a number".to_string(), "-x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_metrics() {
+        let lists = LocationLists::new(vec![vec![1, 5, 9], vec![9, 2, 5], vec![1, 9, 5]]);
+        assert_eq!(lists.distance(0, 1, Metric::Manhattan), Ok(1));
+        assert_eq!(lists.distance(0, 1, Metric::SquaredEuclidean), Ok(1));
+        assert_eq!(lists.distance(0, 1, Metric::Chebyshev), Ok(1));
+        assert_eq!(lists.distance(0, 1, Metric::Mismatches), Ok(1));
+        assert_eq!(lists.distance(0, 2, Metric::Mismatches), Ok(0));
+        assert_eq!(LocationLists::new(vec![vec![], vec![]]).distance(0, 1, Metric::Chebyshev), Ok(0));
+
+        // Beyond i32 ids, which overflow once squared
+        let lists = LocationLists::new(vec![vec![0], vec![3_000_000_000]]);
+        assert_eq!(lists.distance(0, 1, Metric::SquaredEuclidean), Ok(9_000_000_000_000_000_000));
+        let lists = LocationLists::new(vec![vec![-7_000_000_000], vec![7_000_000_000]]);
+        assert_eq!(lists.distance(0, 1, Metric::Chebyshev), Ok(14_000_000_000));
+        assert_eq!(lists.distance(0, 1, Metric::SquaredEuclidean), Err(Overflow));
+        assert_eq!(lists.similarity_score(0, 1), Ok(0));
+        assert_eq!(LocationLists::new(vec![vec![i64::MAX; 2], vec![i64::MAX]]).similarity_score(0, 1), Err(Overflow));
+    }
+
+    #[test]
+    fn test_histogram_and_shared() {
+        let lists = LocationLists::new(vec![vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3, 1]]);
+        assert_eq!(lists.histogram(0), vec![(1, 1), (2, 1), (3, 3), (4, 1)]);
+        assert_eq!(lists.top_shared(0, 1, 2), vec![
+            Shared { value: 3, counts: (3, 3) },
+            Shared { value: 1, counts: (1, 1) },
+        ]);
+        assert_eq!(lists.top_shared(0, 1, 10).len(), 3);
+        assert_eq!(lists.similarity_score(1, 0), lists.similarity_score(0, 1));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_sort_ids(mut ids in proptest::collection::vec(proptest::prelude::any::<i64>(), 0..100), small in 0..200_i64) {
+            // Both the radix sort (any ids) and the counting sort (small ids)
+            let mut small_ids = ids.iter().map(|id| id % small.max(1)).collect::<Vec<_>>();
+            for ids in [&mut ids, &mut small_ids] {
+                let mut expected = ids.clone();
+                expected.sort_unstable();
+                sort_ids(ids);
+                proptest::prop_assert_eq!(ids, &expected);
+            }
+        }
+    }
+}
@@ -1,29 +1,30 @@
-//use std::env;
-use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::process;
 
-use day1::*;
+use day1::columns::read_columns;
+use solution::Answer;
 
-static _SAMPLE_INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+static _SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+static INPUT_FILE_PATH: &str = "data/input.txt";
 
 fn main() {
     // Sample input
-    //let input = _SAMPLE_INPUT.to_string();
+    //let reader = _SAMPLE_INPUT.as_bytes();
 
-    // Real/File input
-    let file_path = "data/day1_part1.txt";
-    let input = fs::read_to_string(file_path)
-        .expect("Should have been able to read the file");
+    // Real/File input, streamed instead of read into a single string
+    let file = File::open(INPUT_FILE_PATH)
+        .expect("Should have been able to open the file");
+    let reader = BufReader::new(file);
 
-    let part1_solution = day1_part1(&input);
+    let columns = read_columns(reader).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = Answer::from(columns.total_distance());
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = day1_part2(&input);
+    let part2_solution = Answer::from(columns.similarity_score());
     println!("Part 2, solution: {part2_solution}");
-
 }
-
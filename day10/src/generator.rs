@@ -0,0 +1,76 @@
+use rand::Rng;
+
+/// Shape of the generated topographic map
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Number of hiking trails (0 to 9, one step at a time) carved into the random heights
+    pub trails: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 47, height: 47, trails: 300 }
+    }
+}
+
+/// Cells of a trail: a walk of 10 distinct cells, each one next to the previous one
+fn trail<R: Rng>(rng: &mut R, width: usize, height: usize) -> Vec<(usize, usize)> {
+    'walk: loop {
+        let mut cells = vec![(rng.random_range(0..height), rng.random_range(0..width))];
+        while cells.len() < 10 {
+            let (row, col) = cells[cells.len() - 1];
+            let neighbours = [(row.wrapping_sub(1), col), (row + 1, col), (row, col.wrapping_sub(1)), (row, col + 1)]
+                .into_iter()
+                .filter(|&(r, c)| r < height && c < width && !cells.contains(&(r, c)))
+                .collect::<Vec<_>>();
+            if neighbours.is_empty() {
+                continue 'walk;
+            }
+            cells.push(neighbours[rng.random_range(0..neighbours.len())]);
+        }
+        return cells;
+    }
+}
+
+/// A map of random heights (one digit per tile) with some trails carved into it.
+/// Later trails may cut through earlier ones.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut grid = (0..options.height)
+        .map(|_| (0..options.width).map(|_| rng.random_range(0..10_u32)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for _ in 0..options.trails {
+        for (height, (row, col)) in trail(rng, options.width, options.height).into_iter().enumerate() {
+            grid[row][col] = height as u32;
+        }
+    }
+
+    grid.iter()
+        .map(|row| row.iter().map(|&h| char::from_digit(h, 10).unwrap()).collect::<String>() + "\n")
+        .collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 6, height: 5, trails: 1 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(10), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(10), &options));
+
+        let map = parse(&input).unwrap();
+        assert_eq!((map.len(), map[0].len()), (5, 6));
+        assert!(part1(&map) >= 1);
+    }
+}
@@ -1,53 +1,57 @@
 use std::collections::HashSet;
 
+use parsing::{char_grid, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
 trait Rectangle {
+    fn adjacent_coordinates(&self, coord: &(usize, usize)) -> std::vec::IntoIter<(usize, usize)>;
     fn adjacent_coordinates_matching(&self, coord: &(usize, usize), number: u32) -> std::vec::IntoIter<(usize, usize)>;
 }
 
 impl Rectangle for Vec<Vec<u32>> {
-    fn adjacent_coordinates_matching(&self, coord: &(usize, usize), number: u32) -> std::vec::IntoIter<(usize, usize)> {
+    fn adjacent_coordinates(&self, coord: &(usize, usize)) -> std::vec::IntoIter<(usize, usize)> {
         let height = self.len();
         let width = self[0].len();
 
         let mut adjacent = Vec::with_capacity(4);
 
-        if (1..height+1).contains(&coord.0    ) && (0..width  ).contains(&coord.1    ) {
-            let c = (coord.0-1, coord.1  );
-            let n = self[c.0][c.1];
-            if number == n { adjacent.push( c ); }
-        }
-        if (0..height  ).contains(&(coord.0+1)) && (0..width  ).contains(&coord.1    ) {
-            let c = (coord.0+1, coord.1  );
-            let n = self[c.0][c.1];
-            if number == n { adjacent.push( c ); }
-        }
-        if (0..height  ).contains(&coord.0    ) && (1..width+1).contains(&coord.1    ) {
-            let c = (coord.0  , coord.1-1);
-            let n = self[c.0][c.1];
-            if number == n { adjacent.push( c ); }
-        }
-        if (0..height  ).contains(&coord.0    ) && (0..width  ).contains(&(coord.1+1)) {
-            let c = (coord.0  , coord.1+1);
-            let n = self[c.0][c.1];
-            if number == n { adjacent.push( c ); }
-        }
+        if (1..height+1).contains(&coord.0    ) && (0..width  ).contains(&coord.1    ) { adjacent.push( (coord.0-1, coord.1  ) ); }
+        if (0..height  ).contains(&(coord.0+1)) && (0..width  ).contains(&coord.1    ) { adjacent.push( (coord.0+1, coord.1  ) ); }
+        if (0..height  ).contains(&coord.0    ) && (1..width+1).contains(&coord.1    ) { adjacent.push( (coord.0  , coord.1-1) ); }
+        if (0..height  ).contains(&coord.0    ) && (0..width  ).contains(&(coord.1+1)) { adjacent.push( (coord.0  , coord.1+1) ); }
 
         adjacent.into_iter()
     }
+
+    fn adjacent_coordinates_matching(&self, coord: &(usize, usize), number: u32) -> std::vec::IntoIter<(usize, usize)> {
+        self.adjacent_coordinates(coord)
+            .filter( |c| self[c.0][c.1] == number )
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
 }
 
-pub fn part1(input: &str) -> usize
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, ParseError>
 {
     // Parse input into a matrix
-    let number_matrix: Vec<Vec<u32>> = input.lines()
+    let number_matrix: Vec<Vec<u32>> = char_grid(input, |c| c.is_ascii_digit(), "a height digit")?
+        .into_iter()
         .map( |l|
         {
-            l.chars()
-                .map( |c| c.to_digit(10).unwrap() )
+            l.into_iter()
+                .filter_map( |c| c.to_digit(10) )
                 .collect::<Vec<u32>>()
         })
         .collect::<Vec<_>>();
 
+    Ok(number_matrix)
+}
+
+pub fn part1(number_matrix: &Vec<Vec<u32>>) -> usize
+{
     // Iterate over the coordinate of each trail head (num = 0)
     number_matrix.iter()
         .enumerate() // index is row, item is Vec<u32>
@@ -76,18 +80,8 @@ pub fn part1(input: &str) -> usize
         .sum()
 }
 
-pub fn part2(input: &str) -> usize
+pub fn part2(number_matrix: &Vec<Vec<u32>>) -> usize
 {
-    // Parse input into a matrix
-    let number_matrix: Vec<Vec<u32>> = input.lines()
-        .map( |l|
-        {
-            l.chars()
-                .map( |c| c.to_digit(10).unwrap() )
-                .collect::<Vec<u32>>()
-        })
-        .collect::<Vec<_>>();
-
     // Iterate over the coordinate of each trail head (num = 0)
     number_matrix.iter()
         .enumerate() // index is row, item is Vec<u32>
@@ -115,11 +109,24 @@ pub fn part2(input: &str) -> usize
         .sum()
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Vec<u32>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    solution::fixture_tests!(Day10: sample);
+
     #[test]
     fn test_add() {
         let data: Vec<Vec<u32>> = vec!{ vec!{1,2,3}, vec!{4, 5, 6}, vec!{7, 8, 9} };
@@ -1,16 +1,10 @@
 use std::fs;
+use std::process;
 
 use day10::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT: &str = "89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -28,10 +22,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let number_matrix = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&number_matrix);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&number_matrix);
     println!("Part 2, solution: {part2_solution}");
 }
 
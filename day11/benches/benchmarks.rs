@@ -0,0 +1,31 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day11::*;
+
+static DAY_11_INPUT: &str = include_str!("../data/input.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+
+    let numbers = parse(DAY_11_INPUT).unwrap();
+    let input_ref = &numbers[..];
+
+    // Brute force strategies cannot reach 75 blinks, compare them on 25
+    let mut group = c.benchmark_group("day11_blink25");
+    group.sample_size(10);
+
+    group.bench_function("grouped",
+        |b| b.iter(|| blink(black_box(input_ref), 25)));
+
+    group.bench_function("multithread",
+        |b| b.iter(|| lib_multithread::blink(black_box(input_ref), 25)));
+
+    group.bench_function("shortcuts",
+        |b| b.iter(|| lib_shortcuts::blink(black_box(input_ref), 25)));
+
+    group.bench_function("flat_map",
+        |b| b.iter(|| part1(black_box(input_ref))));
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
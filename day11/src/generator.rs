@@ -0,0 +1,55 @@
+use rand::Rng;
+
+/// Shape of the generated stones
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub stones: usize,
+    /// Largest number of digits engraved on a stone
+    pub max_digits: u32,
+    /// Chance of a stone being engraved with 0
+    pub zeros: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { stones: 8, max_digits: 7, zeros: 0.1 }
+    }
+}
+
+/// Numbers engraved on the stones, separated by spaces
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let stones = (0..options.stones)
+        .map(|_| match rng.random_bool(options.zeros) {
+            true  => 0,
+            false => {
+                let digits = rng.random_range(1..=options.max_digits.clamp(1, 19));
+                rng.random_range(1..10_u64.pow(digits))
+            }
+        })
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    stones.join(" ") + "\n"
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { stones: 20, max_digits: 3, zeros: 0.0 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(11), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(11), &options));
+
+        let stones = parse(&input).unwrap();
+        assert_eq!(stones.len(), 20);
+        assert!(stones.iter().all(|&n| (1..1000).contains(&n)));
+    }
+}
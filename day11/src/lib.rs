@@ -1,5 +1,14 @@
 #![recursion_limit = "256"]
 
+use parsing::{parse_list, space_list, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
+pub mod lib_multithread;
+pub mod lib_shortcuts;
+
 pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
     if number == 0
     {
@@ -21,13 +30,15 @@ pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
     }
 }
 
-pub fn part1(input: &str) -> usize
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError>
 {
-    let numbers = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .collect::<Vec<_>>();
+    parse_list(input, space_list(input), "a stone number")
+}
 
-    numbers.into_iter()
+pub fn part1(numbers: &[u64]) -> usize
+{
+    numbers.iter()
+        .copied()
         .flat_map( magic )
         .flat_map( magic )
         .flat_map( magic )
@@ -79,7 +90,7 @@ impl Stones {
         {
             let length = self.number.ilog10() + 1;
 
-            if length % 2 == 0 {
+            if length.is_multiple_of(2) {
                 let base = 10_u64.pow(length/2);
                 let lhs = self.number / base;
                 let rhs = self.number % base;
@@ -116,16 +127,14 @@ fn sort_and_group_stones(stones: &mut Vec<Stones>) {
 }
 
 
-pub fn part2(input: &str) -> u64
+/// Number of stones after blinking `iterations` times
+pub fn blink(numbers: &[u64], iterations: usize) -> u64
 {
-    let iterations = 75;
-
-    let mut stones = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .map( |number| Stones { number, count: 1 })
+    let mut stones = numbers.iter()
+        .map( |&number| Stones { number, count: 1 })
         .collect::<Vec<_>>();
 
-    for _iteration in 0..iterations {
+    for iteration in 0..iterations {
         // Prepare overhead vector
         let mut added_stones= Vec::with_capacity(stones.len());
 
@@ -143,13 +152,13 @@ pub fn part2(input: &str) -> u64
             });
 
         // Join with overhead
-        stones.extend(added_stones.into_iter());
+        stones.extend(added_stones);
 
         // Sort and group stones
         sort_and_group_stones(&mut stones);
 
-        //println!("Iteration: {_iteration}, len: {}", stones.len());
-        //println!("Iteration: {_iteration} -> {:?}", stones.iter().map(|s| (s.number, s.count)).collect::<Vec<_>>());
+        trace::debug!("Iteration: {iteration}, distinct stones: {}", stones.len());
+        trace::trace!("Iteration: {iteration} -> {:?}", stones.iter().map(|s| (s.number, s.count)).collect::<Vec<_>>());
     }
 
     stones.into_iter()
@@ -157,12 +166,30 @@ pub fn part2(input: &str) -> u64
         .sum()
 }
 
+pub fn part2(numbers: &[u64]) -> u64
+{
+    blink(numbers, 75)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
 
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    solution::fixture_tests!(Day11: sample, sample_small);
+
     #[test]
     fn test_sort_and_group_stones() {
         let mut input = vec![
@@ -171,12 +198,41 @@ mod tests {
             Stones{number: 1, count: 3},
             Stones{number: 3, count: 4}
         ];
-        let output = vec![
-            Stones{number: 1, count: 4},
+        let output = [Stones{number: 1, count: 4},
             Stones{number: 2, count: 2},
-            Stones{number: 3, count: 4}
-        ];
+            Stones{number: 3, count: 4}];
         sort_and_group_stones(&mut input);
         assert_eq!(&input[..], &output[..]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_blink_strategies() {
+        let numbers = [125, 17];
+        assert_eq!(blink(&numbers, 25), 55312);
+        assert_eq!(lib_multithread::blink(&numbers, 25), 55312);
+        assert_eq!(lib_shortcuts::blink(&numbers, 25), 55312);
+        assert_eq!(lib_multithread::part1(&numbers), part1(&numbers));
+        assert_eq!(lib_shortcuts::part1(&numbers), part1(&numbers));
+    }
+
+    #[test]
+    fn test_blink_edge_cases() {
+        for blink in [blink, lib_multithread::blink, lib_shortcuts::blink] {
+            assert_eq!(blink(&[], 25), 0);
+            assert_eq!(blink(&[125, 17], 0), 2);
+        }
+    }
+
+    proptest::proptest! {
+        // Small stones and more than 25 blinks go through the shortcuts
+        #[test]
+        fn test_blink_variants_agree(
+            numbers in proptest::collection::vec(0..1000_u64, 0..4),
+            iterations in 0..28_usize
+        ) {
+            let stones = blink(&numbers, iterations);
+            proptest::prop_assert_eq!(lib_multithread::blink(&numbers, iterations), stones);
+            proptest::prop_assert_eq!(lib_shortcuts::blink(&numbers, iterations), stones);
+        }
+    }
+}
@@ -1,5 +1,3 @@
-#![recursion_limit = "256"]
-
 use std::{thread};
 
 pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
@@ -12,7 +10,7 @@ pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
         let txt = format!("{number}");
         let length = txt.len();
 
-        if length % 2 == 0 {
+        if length.is_multiple_of(2) {
             let lhs = txt[0..length/2].parse::<u64>().unwrap();
             let rhs = txt[length/2..length].parse::<u64>().unwrap();
             vec!{lhs, rhs}.into_iter()
@@ -23,13 +21,10 @@ pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
     }
 }
 
-pub fn part1(input: &str) -> usize
+pub fn part1(numbers: &[u64]) -> usize
 {
-    let numbers = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .collect::<Vec<_>>();
-
-    numbers.into_iter()
+    numbers.iter()
+        .copied()
         .flat_map( magic )
         .flat_map( magic )
         .flat_map( magic )
@@ -84,7 +79,7 @@ impl Stone {
         {
             let length = self.number.ilog10() + 1;
 
-            if length % 2 == 0 {
+            if length.is_multiple_of(2) {
                 let base = 10_u64.pow(length/2);
                 let lhs = self.number / base;
                 let rhs = self.number % base;
@@ -100,13 +95,11 @@ impl Stone {
 }
 
 
-pub fn part2(input: &str) -> u64
+/// Number of stones after blinking `iterations` times
+pub fn blink(numbers: &[u64], iterations: usize) -> u64
 {
-    let iterations = 55;
-
-    let numbers = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .map( |number| Stone { iterations_left: iterations, number })
+    let numbers = numbers.iter()
+        .map( |&number| Stone { iterations_left: iterations, number })
         .collect::<Vec<_>>();
 
     let handles = numbers.into_iter()
@@ -119,9 +112,14 @@ pub fn part2(input: &str) -> u64
 
                 let mut total = 0_u64;
 
-                loop {
-                    // Look at the stone at the back and blink it
-                    let stone = numbers.last_mut().unwrap();
+                // Look at the stone at the back and blink it, unless it is out of iterations
+                while let Some(stone) = numbers.last_mut() {
+                    if stone.iterations_left == 0 {
+                        total += 1;
+                        numbers.pop();
+                        continue;
+                    }
+
                     let maybe_new_stone = stone.blink();
 
                     // If the stone is out of iterations, count it and pop it
@@ -139,20 +137,19 @@ pub fn part2(input: &str) -> u64
                             numbers.push(new_stone);
                         }
                     }
-
-                    if numbers.is_empty() {
-                        break;
-                    }
                 }
                 total
             })
         })
     .collect::<Vec<_>>();
 
-    println!("Threads started");
-
     handles.into_iter()
         .map(|h| h.join() )
-        .map(|r| r.expect("Error") )
+        .map(|r| r.unwrap_or_else(|panic| std::panic::resume_unwind(panic)) )
         .sum()
 }
+
+pub fn part2(numbers: &[u64]) -> u64
+{
+    blink(numbers, 75)
+}
@@ -1,4 +1,4 @@
-#![recursion_limit = "256"]
+use std::sync::OnceLock;
 
 pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
     if number == 0
@@ -10,7 +10,7 @@ pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
         let txt = format!("{number}");
         let length = txt.len();
 
-        if length % 2 == 0 {
+        if length.is_multiple_of(2) {
             let lhs = txt[0..length/2].parse::<u64>().unwrap();
             let rhs = txt[length/2..length].parse::<u64>().unwrap();
             vec!{lhs, rhs}.into_iter()
@@ -21,13 +21,10 @@ pub fn magic(number: u64) -> impl IntoIterator<Item=u64> {
     }
 }
 
-pub fn part1(input: &str) -> usize
+pub fn part1(numbers: &[u64]) -> usize
 {
-    let numbers = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .collect::<Vec<_>>();
-
-    numbers.into_iter()
+    numbers.iter()
+        .copied()
         .flat_map( magic )
         .flat_map( magic )
         .flat_map( magic )
@@ -82,7 +79,7 @@ impl Stone {
         {
             let length = self.number.ilog10() + 1;
 
-            if length % 2 == 0 {
+            if length.is_multiple_of(2) {
                 let base = 10_u64.pow(length/2);
                 let lhs = self.number / base;
                 let rhs = self.number % base;
@@ -97,19 +94,26 @@ impl Stone {
     }
 }
 
+/// Stones below this number take a shortcut of `PREPOPULATION_SHORTCUT_BLINKS` blinks
+const PREPOPULATION_END: u64 = 100;
+const PREPOPULATION_SHORTCUT_BLINKS: usize = 25;
+
+/// Stones that each number below `PREPOPULATION_END` becomes, built by the first call to `blink`
+static SHORTCUT: OnceLock<Vec<Vec<u64>>> = OnceLock::new();
+
 fn blink_n_times(initial_number: u64, times: usize) -> Vec<u64> {
     let mut stones = Vec::with_capacity(128);
     stones.push(Stone{iterations_left: times, number: initial_number});
 
-    loop {
-        // Take the first stone the needs blinking or break
-        let maybe_stone = stones.iter_mut()
-            .filter(|stone| stone.iterations_left > 0 )
-            .next();
-
-        let Some(stone) = maybe_stone else {
-            break;
-        };
+    // Stones before the cursor do not need blinking anymore
+    let mut cursor = 0;
+    while cursor < stones.len() {
+        // Take the first stone the needs blinking
+        let stone = &mut stones[cursor];
+        if stone.iterations_left == 0 {
+            cursor += 1;
+            continue;
+        }
 
         // Blink it
         let maybe_new_stone = stone.blink();
@@ -126,30 +130,30 @@ fn blink_n_times(initial_number: u64, times: usize) -> Vec<u64> {
         .collect::<Vec<_>>()
 }
 
-pub fn part2(input: &str) -> u64
+/// Number of stones after blinking `iterations` times
+pub fn blink(numbers: &[u64], iterations: usize) -> u64
 {
-    let iterations = 75;
-
-    let mut numbers = input.split_ascii_whitespace()
-        .map( |substr| substr.parse::<u64>().unwrap() )
-        .map( |number| Stone { iterations_left: iterations, number })
+    let mut numbers = numbers.iter()
+        .map( |&number| Stone { iterations_left: iterations, number })
         .collect::<Vec<_>>();
 
     let mut total: u64 = 0;
 
 
-    // Prepopulation step
-    let prepopulation_end: u64 = 100;
-    let prepopulation_shortcut_blinks: usize = 25;
-    let shortcut = (0..prepopulation_end)
-        .map(|number| blink_n_times(number, prepopulation_shortcut_blinks) )
-        .collect::<Vec<_>>();
-    println!("Prepopulation done");
+    // Prepopulation step, shared by every call
+    let prepopulation_shortcut_blinks = PREPOPULATION_SHORTCUT_BLINKS;
+    let shortcut = SHORTCUT.get_or_init(|| (0..PREPOPULATION_END)
+        .map(|number| blink_n_times(number, PREPOPULATION_SHORTCUT_BLINKS) )
+        .collect::<Vec<_>>());
 
 
-    loop {
-        // Look at the stone at the back and blink it
-        let stone = numbers.last_mut().unwrap();
+    // Look at the stone at the back and blink it, unless it is out of iterations
+    while let Some(stone) = numbers.last_mut() {
+        if stone.iterations_left == 0 {
+            total += 1;
+            numbers.pop();
+            continue;
+        }
 
         // Try to take the shortcut
         if stone.number < (shortcut.len() as u64) && stone.iterations_left >= prepopulation_shortcut_blinks{
@@ -188,17 +192,12 @@ pub fn part2(input: &str) -> u64
                 }
             }
         }
-
-        if numbers.is_empty() {
-            break;
-        }
-        //else {
-        //    let temp = numbers.iter()
-        //        .map(|el| el.number)
-        //        .collect::<Vec<_>>();
-        //    println!("{:?}, IT-left: {}, total: {}", temp, numbers.last().unwrap().iterations_left, total);
-        //}
     }
 
     total
 }
+
+pub fn part2(numbers: &[u64]) -> u64
+{
+    blink(numbers, 75)
+}
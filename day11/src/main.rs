@@ -1,10 +1,10 @@
 use std::fs;
+use std::process;
 
 use day11::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-//static SAMPLE_INPUT: &str = "0 1 10 99 999";
-static SAMPLE_INPUT: &str = "125 17";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -22,10 +22,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let numbers = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&numbers);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&numbers);
     println!("Part 2, solution: {part2_solution}");
 }
 
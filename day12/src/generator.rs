@@ -0,0 +1,69 @@
+use rand::Rng;
+
+/// Shape of the generated garden
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Number of plant types, up to 26
+    pub plants: usize,
+    /// Number of smoothing passes, each one making regions bigger and their borders less ragged
+    pub smoothing: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 140, height: 140, plants: 26, smoothing: 6 }
+    }
+}
+
+/// A garden of plots, one uppercase letter per plant type. It starts as random noise, and every
+/// smoothing pass replaces each plot by the plant of a random neighbour, so plants group into regions.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let plants = ('A'..='Z').take(options.plants.clamp(1, 26)).collect::<Vec<_>>();
+    let mut grid = (0..options.height)
+        .map(|_| (0..options.width).map(|_| plants[rng.random_range(0..plants.len())]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for _ in 0..options.smoothing {
+        let previous = grid.clone();
+        for (row, line) in grid.iter_mut().enumerate() {
+            for (col, plot) in line.iter_mut().enumerate() {
+                let (r, c) = match rng.random_range(0..4) {
+                    0 => (row.saturating_sub(1), col),
+                    1 => ((row + 1).min(options.height - 1), col),
+                    2 => (row, col.saturating_sub(1)),
+                    _ => (row, (col + 1).min(options.width - 1)),
+                };
+                *plot = previous[r][c];
+            }
+        }
+    }
+
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use rectangle::Rectangular;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 9, height: 4, plants: 1, smoothing: 2 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(12), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(12), &options));
+
+        // A single region: area 36, perimeter 26
+        let garden = parse(&input).unwrap();
+        assert_eq!((garden.get_height(), garden.get_width()), (4, 9));
+        assert_eq!(part1(&garden), 36 * 26);
+    }
+}
@@ -2,10 +2,20 @@ use std::collections::HashSet;
 
 use rectangle::{Rectangle, RectangleEdge, Rectangular, RectangularData};
 
-pub fn part1(input: &str) -> usize
+use parsing::{char_grid, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
+pub fn parse(input: &str) -> Result<Rectangle<char>, ParseError>
 {
-    let r: Rectangle<char> = Rectangle::from_char_str(input).unwrap();
+    let plots = char_grid(input, |c| c.is_ascii_uppercase(), "a plant type letter")?;
+    Ok( Rectangle::from_vec(plots)? )
+}
 
+pub fn part1(r: &Rectangle<char>) -> usize
+{
     let mut global_visited = HashSet::new();
 
     let mut result: usize = 0;
@@ -41,7 +51,7 @@ pub fn part1(input: &str) -> usize
             );
         }
 
-        // println!("Char `{c}` has {} cells: {:?}", current_group.len(), current_group);
+        trace::trace!("Char `{c}` has {} cells: {:?}", current_group.len(), current_group);
 
         // Calculate area and perimeter
         let area: usize = current_group.len();
@@ -55,17 +65,15 @@ pub fn part1(input: &str) -> usize
                 beyond_limit_cells + other_cells
             })
             .sum();
-        // println!("Perimeter: {perimeter}, Area: {area}");
+        trace::debug!("Char `{c}`: perimeter {perimeter}, area {area}");
         result += area * perimeter;
     }
 
     result
 }
 
-pub fn part2(input: &str) -> usize
+pub fn part2(r: &Rectangle<char>) -> usize
 {
-    let r: Rectangle<char> = Rectangle::from_char_str(input).unwrap();
-
     let mut global_visited = HashSet::new();
 
     let mut result: usize = 0;
@@ -103,7 +111,7 @@ pub fn part2(input: &str) -> usize
 
         // Collect all the edges into a set
         let edge_set: HashSet<_> = current_group.iter()
-            .flat_map( |c| r.adjacent_coordinates(c).into_iter().zip(r.edges(c).into_iter() )) // Adjacent coords and edges are aligned
+            .flat_map( |c| r.adjacent_coordinates(c).into_iter().zip(r.edges(c) )) // Adjacent coords and edges are aligned
             .filter_map(|(maybe_coord, edge)| {
 
                 // If coord is None, this is beyond the rectangle, so it is an edge
@@ -164,10 +172,32 @@ pub fn part2(input: &str) -> usize
 
         let total_sides = top_sides + bottom_sides + left_sides + right_sides;
 
-        // println!("Current group `{c}` has size: {}, total sides: {total_sides} = ({top_sides} + {bottom_sides} + {left_sides} + {right_sides})", current_group.len());
+        trace::debug!("Char `{c}`: area {}, total sides {total_sides} = ({top_sides} + {bottom_sides} + {left_sides} + {right_sides})", current_group.len());
 
         result += total_sides * current_group.len();
     }
 
     result
 }
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Rectangle<char>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day12: sample, sample_abcde, sample_xo, sample_ab);
+}
@@ -1,36 +1,10 @@
 use std::fs;
+use std::process;
 
 use day12::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-// static SAMPLE_INPUT: &str = "AAAA
-// BBCD
-// BBCC
-// EEEC";
-
-// static SAMPLE_INPUT: &str = "OOOOO
-// OXOXO
-// OOOOO
-// OXOXO
-// OOOOO";
-
-// static SAMPLE_INPUT: &str = "AAAAAA
-// AAABBA
-// AAABBA
-// ABBAAA
-// ABBAAA
-// AAAAAA";
-
-static SAMPLE_INPUT: &str = "RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -48,10 +22,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let garden = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&garden);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&garden);
     println!("Part 2, solution: {part2_solution}");
 }
 
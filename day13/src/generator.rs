@@ -0,0 +1,64 @@
+use rand::Rng;
+
+/// Shape of the generated claw machines
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub machines: usize,
+    /// Chance of the prize being reachable with at most 100 presses of each button
+    pub winnable: f64,
+    /// Chance of button B moving in the same direction as button A
+    pub parallel: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { machines: 320, winnable: 0.5, parallel: 0.0 }
+    }
+}
+
+/// Claw machines of three lines (button A, button B and prize), separated by empty lines
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let machines = (0..options.machines)
+        .map(|_| {
+            let a = (rng.random_range(10..100_i64), rng.random_range(10..100_i64));
+            let b = match rng.random_bool(options.parallel) {
+                true  => (a.0 * 2, a.1 * 2),
+                false => (rng.random_range(10..100_i64), rng.random_range(10..100_i64)),
+            };
+            let prize = match rng.random_bool(options.winnable) {
+                true  => {
+                    let (presses_a, presses_b) = (rng.random_range(0..=100), rng.random_range(0..=100));
+                    (a.0 * presses_a + b.0 * presses_b, a.1 * presses_a + b.1 * presses_b)
+                }
+                false => (rng.random_range(1000..20000), rng.random_range(1000..20000)),
+            };
+            format!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n", a.0, a.1, b.0, b.1, prize.0, prize.1)
+        })
+        .collect::<Vec<_>>();
+    machines.join("\n")
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { machines: 30, winnable: 1.0, parallel: 0.5 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(13), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(13), &options));
+        assert_eq!(parse(&input).unwrap().len(), 30);
+
+        // Random prizes are seldom reachable, so they cost less tokens
+        let options = Options { winnable: 0.0, ..options };
+        let machines = parse(&generate(&mut ChaCha8Rng::seed_from_u64(13), &options)).unwrap();
+        assert!(part1(&machines) < part1(&parse(&input).unwrap()));
+    }
+}
@@ -1,18 +1,48 @@
-use regex::Regex;
-use itertools::Itertools;
 use std::cmp::min;
 
-pub fn part1(input: &str) -> i32
+use parsing::{exactly, sections, unsigned_integers, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
+/// Claw machine: button A (x, y), button B (x, y) and prize (x, y)
+pub type ClawMachine = (i64, i64, i64, i64, i64, i64);
+
+pub fn parse(input: &str) -> Result<Vec<ClawMachine>, ParseError>
+{
+    let patterns = [
+        ("Button A: ", "`Button A: X+<number>, Y+<number>`"),
+        ("Button B: ", "`Button B: X+<number>, Y+<number>`"),
+        ("Prize: ",    "`Prize: X=<number>, Y=<number>`"),
+    ];
+
+    // Each machine is described by 3 lines, machines are separated by empty lines
+    sections(input)
+        .map( |section| {
+            let mut lines = section.lines();
+            let mut pairs = [[0, 0]; 3];
+            for (idx, (prefix, expected)) in patterns.iter().enumerate() {
+                let line = lines.next()
+                    .ok_or_else(|| ParseError::missing(&format!("{expected} line after line {}", section.lines().count())))?;
+                if !line.starts_with(prefix) {
+                    return Err(ParseError::unexpected(input, line, expected));
+                }
+                pairs[idx] = exactly(unsigned_integers(input, line), line, expected)?;
+            }
+            if let Some(line) = lines.next() {
+                return Err(ParseError::unexpected(input, line, "an empty line between machines"));
+            }
+            let [[a_x, a_y], [b_x, b_y], [p_x, p_y]] = pairs;
+            Ok( (a_x, a_y, b_x, b_y, p_x, p_y) )
+        })
+        .collect()
+}
+
+pub fn part1(machines: &[ClawMachine]) -> i64
 {
-    let re = Regex::new(r".+X\+(\d+), Y\+(\d+)\n.+X\+(\d+), Y\+(\d+)\n.+X=(\d+), Y=(\d+)").unwrap();
-
-    let total_cost: i32 = re.captures_iter(input)
-        .map( |caps| caps.iter()
-            .skip(1)
-            .map( |c| c.unwrap().as_str().parse::<i32>().unwrap() )
-            .collect_tuple::<(i32, i32, i32, i32, i32, i32)>()
-            .unwrap()
-        )
+    let total_cost: i64 = machines.iter()
+        .copied()
         .filter_map(|(a_x, a_y, b_x, b_y, p_x, p_y)| {
 
             // Calculate the determinant of the matrix
@@ -58,17 +88,10 @@ pub fn part1(input: &str) -> i32
     total_cost
 }
 
-pub fn part2(input: &str) -> i64
+pub fn part2(machines: &[ClawMachine]) -> i64
 {
-    let re = Regex::new(r".+X\+(\d+), Y\+(\d+)\n.+X\+(\d+), Y\+(\d+)\n.+X=(\d+), Y=(\d+)").unwrap();
-
-    let total_cost: i64 = re.captures_iter(input)
-        .map( |caps| caps.iter()
-            .skip(1)
-            .map( |c| c.unwrap().as_str().parse::<i64>().unwrap() )
-            .collect_tuple::<(i64, i64, i64, i64, i64, i64)>()
-            .unwrap()
-        )
+    let total_cost: i64 = machines.iter()
+        .copied()
         .map(|(a_x, a_y, b_x, b_y, p_x, p_y)| (a_x, a_y, b_x, b_y, p_x + 10000000000000, p_y + 10000000000000) ) // Add coordinates
         .filter_map(|(a_x, a_y, b_x, b_y, p_x, p_y)| {
 
@@ -113,4 +136,26 @@ pub fn part2(input: &str) -> i64
         .sum();
 
     total_cost
-}
\ No newline at end of file
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<ClawMachine>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day13: sample);
+}
@@ -1,23 +1,10 @@
 use std::fs;
+use std::process;
 
 use day13::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT: &str = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -35,10 +22,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let machines = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&machines);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&machines);
     println!("Part 2, solution: {part2_solution}");
 }
 
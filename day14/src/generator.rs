@@ -0,0 +1,51 @@
+use rand::Rng;
+
+use crate::Area;
+
+/// Shape of the generated robots
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub robots: usize,
+    /// Area the robots move in, which must also be given to the solution
+    pub area: Area
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { robots: 500, area: Area::default() }
+    }
+}
+
+/// Robots like `p=0,4 v=3,-3`, with a position inside the area and a speed below its size
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let Area { width, height } = options.area;
+
+    (0..options.robots)
+        .map(|_| format!("p={},{} v={},{}\n",
+            rng.random_range(0..width), rng.random_range(0..height),
+            rng.random_range(-width + 1..width), rng.random_range(-height + 1..height)))
+        .collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { robots: 40, area: Area { width: 11, height: 7 } };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(14), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(14), &options));
+
+        let robots = parse(&input).unwrap();
+        assert_eq!(robots.len(), 40);
+        assert!(robots.iter().all(|&(x, y, vx, vy)| (0..11).contains(&x) && (0..7).contains(&y) && vx.abs() < 11 && vy.abs() < 7));
+    }
+}
@@ -1,22 +1,36 @@
 use std::collections::HashSet;
-use regex::Regex;
-use itertools::Itertools;
 
-pub fn part1(input: &str, width: i32, height: i32) -> i32
+use parsing::{exactly, signed_integers, unsigned_integers, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
+/// Robot position (x, y) and velocity (vx, vy)
+pub type Robot = (i32, i32, i32, i32);
+
+pub fn parse(input: &str) -> Result<Vec<Robot>, ParseError>
 {
-    let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
+    // Parse data
+    input.lines()
+        .map( |line| {
+            let expected = "a robot like `p=0,4 v=3,-3`";
+            if !line.starts_with("p=") || !line.contains(" v=") {
+                return Err(ParseError::unexpected(input, line, expected));
+            }
+            let [p_x, p_y, v_x, v_y] = exactly(signed_integers(input, line), line, expected)?;
+            Ok( (p_x, p_y, v_x, v_y) )
+        })
+        .collect()
+}
 
+pub fn part1(robots: &[Robot], width: i32, height: i32) -> i32
+{
     let iterations = 100;
     let half_width = width / 2;
     let half_height = height / 2;
 
-    re.captures_iter(input)
-        .map( |caps| caps.iter()
-            .skip(1)
-            .map( |c| c.unwrap().as_str().parse::<i32>().unwrap() )
-            .collect_tuple::<(i32, i32, i32, i32)>()
-            .unwrap()
-        )
+    robots.iter()
         .map(|(x,y, vx, vy)| {
             ((((x + iterations * vx) %  width) +  width) %  width,
              (((y + iterations * vy) % height) + height) % height)
@@ -32,21 +46,8 @@ pub fn part1(input: &str, width: i32, height: i32) -> i32
         .product()
 }
 
-pub fn part2(input: &str, width: i32, height: i32) -> i32
+pub fn part2(parsed_data: &[Robot], width: i32, height: i32) -> i32
 {
-    // Build regexp
-    let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
-
-    // Parse data
-    let parsed_data = re.captures_iter(input)
-        .map( |caps| caps.iter()
-            .skip(1)
-            .map( |c| c.unwrap().as_str().parse::<i32>().unwrap() )
-            .collect_tuple::<(i32, i32, i32, i32)>()
-            .unwrap()
-        )
-        .collect::<Vec<_>>();
-
     // For each number of iterations between 0 and 10k
     let iterations_for_image = (0..10000)
         .map(|iterations| {
@@ -72,8 +73,6 @@ pub fn part2(input: &str, width: i32, height: i32) -> i32
         .map(|(iterations, _)| iterations ) //
         .unwrap();
 
-    print_robots(parsed_data, width, height, iterations_for_image);
-
     iterations_for_image
 }
 
@@ -91,7 +90,8 @@ fn neighbor_coords(coord: &(i32, i32)) -> [(i32, i32); 8] {
     ]
 }
 
-fn print_robots(robot_data: Vec<(i32, i32, i32, i32)>, width: i32, height:i32, iterations: i32) {
+/// Draw the robots after some iterations, on stderr so that it does not mix with the answers
+pub fn print_robots(robot_data: &[Robot], width: i32, height:i32, iterations: i32) {
     // Calculate the positions the
     let image_positions = robot_data.iter()
     .map(|(x,y, vx, vy)| {
@@ -109,5 +109,58 @@ fn print_robots(robot_data: Vec<(i32, i32, i32, i32)>, width: i32, height:i32, i
         output += "\n";
     }
 
-    println!("{output}");
+    eprintln!("{output}");
+}
+
+/// Size of the area the robots move in
+#[derive(Clone, Copy, Debug)]
+pub struct Area {
+    pub width: i32,
+    pub height: i32
+}
+
+impl Default for Area {
+    fn default() -> Self {
+        Area { width: 101, height: 103 }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Params = Area;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, area: &Area) -> Answer { part1(input, area.width, area.height).into() }
+    fn part2(input: &Self::Input, area: &Area) -> Answer { part2(input, area.width, area.height).into() }
+
+    /// `<width> <height>`, neither of them 0
+    fn params(text: &str) -> Result<Area, ParseError> {
+        let [width, height] = exactly(unsigned_integers(text, text), text, "`<width> <height>`")?;
+        if width == 0 || height == 0 {
+            return Err(ParseError::unexpected(text, text, "`<width> <height>` of at least 1"));
+        }
+        Ok( Area { width, height } )
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day14: sample, sample_pattern);
+
+    #[test]
+    fn test_params() {
+        assert_eq!(Day14::params("11 7").map(|area| (area.width, area.height)), Ok((11, 7)));
+        assert_eq!(
+            Day14::params("0 7").err(),
+            Some(ParseError::Unexpected(1, 1, "`<width> <height>` of at least 1".to_string(), "0 7".to_string()))
+        );
+    }
 }
@@ -0,0 +1,76 @@
+use rand::Rng;
+
+/// Characters of the moves
+static MOVES: [char; 4] = ['^', 'v', '<', '>'];
+
+/// Shape of the generated warehouse and moves
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Size of the warehouse, including the walls around it
+    pub width: usize,
+    pub height: usize,
+    /// Chance of an inner tile being a wall
+    pub walls: f64,
+    /// Chance of an inner tile being a box
+    pub boxes: f64,
+    pub moves: usize,
+    /// Moves per line
+    pub line_length: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 50, height: 50, walls: 0.05, boxes: 0.3, moves: 20000, line_length: 1000 }
+    }
+}
+
+/// A warehouse surrounded by walls, with boxes (`O`) and a robot (`@`), an empty line, then lines of moves
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let (width, height) = (options.width.max(3), options.height.max(3));
+
+    let mut grid = (0..height)
+        .map(|row| (0..width)
+            .map(|col| match (row, col) {
+                _ if row == 0 || col == 0 || row == height - 1 || col == width - 1 => '#',
+                _ if rng.random_bool(options.walls)                                => '#',
+                _ if rng.random_bool(options.boxes)                                => 'O',
+                _                                                                  => '.',
+            })
+            .collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    grid[rng.random_range(1..height - 1)][rng.random_range(1..width - 1)] = '@';
+
+    let mut output = grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect::<String>();
+    output += "\n";
+
+    let moves = (0..options.moves).map(|_| MOVES[rng.random_range(0..4)]).collect::<Vec<_>>();
+    for line in moves.chunks(options.line_length.max(1)) {
+        output.extend(line);
+        output += "\n";
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1, part2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 12, height: 8, walls: 0.1, boxes: 0.0, moves: 95, line_length: 10 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(15), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(15), &options));
+
+        let setup = parse(&input).unwrap();
+        assert_eq!(setup.moves.len(), 95);
+        // Without boxes there is nothing to sum
+        assert_eq!((part1(&setup), part2(&setup)), (0, 0));
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::VecDeque;
 
 use rectangle::{Rectangle, Rectangular, RectangularData, Direction};
-use itertools::Itertools;
+
+use parsing::{char_grid, location, split_sections, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
 
 
 fn dir_from_char(ch: char) -> Option<Direction> {
@@ -148,42 +153,62 @@ trait Warehouse: RectangularData<char> + Rectangular {
 // This is the lazy way
 impl Warehouse for Rectangle<char> { }
 
-pub fn part1(input: &str) -> usize
+/// Initial warehouse layout and the list of robot moves
+pub struct WarehouseSetup {
+    pub warehouse: Rectangle<char>,
+    pub moves: Vec<Direction>
+}
+
+pub fn parse(input: &str) -> Result<WarehouseSetup, ParseError>
 {
-    let (warehouse, instructions) = input.split("\n\n")
-        .collect_tuple::<(&str, &str)>()
-        .unwrap_or_else(|| {
-            input.split("\r\n\r\n")
-                .collect_tuple::<(&str, &str)>()
-                .expect("Failed to split input")
-        });
+    let (warehouse, instructions) = split_sections(input, "the warehouse and the moves")?;
 
-    let mut warehouse = Rectangle::from_char_str(warehouse)
-        .map_err( |error|  panic!("Failed to parse rectangle. Reason: {error}") )
-        .unwrap();
+    let warehouse = Rectangle::from_vec(char_grid(warehouse, |c| "#.O@".contains(c), "`#`, `.`, `O` or `@`")?)?;
+    if !warehouse.iter_coord().any(|c| *warehouse.get(&c).unwrap() == '@' ) {
+        return Err(ParseError::missing("robot (`@`) in the warehouse"));
+    }
+
+    // Walls all around, so that nothing is ever pushed out of the warehouse
+    let (height, width) = (warehouse.get_height(), warehouse.get_width());
+    let gap = warehouse.iter_coord()
+        .filter(|c| c.0 == 0 || c.0 == height - 1 || c.1 == 0 || c.1 == width - 1 )
+        .find(|c| *warehouse.get(c).unwrap() != '#' );
+    if let Some((row, col)) = gap {
+        let found = warehouse.get(&(row, col)).unwrap().to_string();
+        return Err(ParseError::Unexpected(row + 1, col + 1, "a wall (`#`) around the warehouse".to_string(), found));
+    }
+
+    let moves = instructions.lines()
+        .flat_map(|line| {
+            let (line_no, _) = location(input, line);
+            line.chars()
+                .enumerate()
+                .map(move |(col, c)| dir_from_char(c)
+                    .ok_or_else(|| ParseError::Unexpected(line_no, col + 1, "a move (`^`, `v`, `<`, `>`)".to_string(), c.to_string())) )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok( WarehouseSetup { warehouse, moves } )
+}
+
+pub fn part1(setup: &WarehouseSetup) -> usize
+{
+    let mut warehouse = setup.warehouse.clone();
 
     let mut robot_position = warehouse.iter_coord()
         .find(|c| *warehouse.get(c).unwrap() == '@' )
         .unwrap_or_else(|| panic!("Failed to locate initial position") );
 
-    let instructions = instructions.lines()
-        .flat_map(|line| line.chars() )
-        .collect::<String>();
-
-    // Before
-    // println!("{}\n", warehouse.to_string());
+    trace::debug!("Before\n{warehouse}");
 
-    instructions.chars()
-        .map(|c| dir_from_char(c).unwrap_or_else(|| panic!("Invalid direction character: {c}")))
+    setup.moves.iter()
+        .copied()
         .for_each(|dir| {
             warehouse.push(&mut robot_position, dir);
-
-            // Step
-            // println!("{}\n", warehouse.to_string());
+            trace::trace!("Move {dir:?}\n{warehouse}");
         });
 
-    // After
-    // println!("{}", warehouse.to_string());
+    trace::debug!("After\n{warehouse}");
 
     warehouse.iter_coord()
         .filter(|&coord| *warehouse.get(&coord).unwrap() == 'O' )
@@ -192,17 +217,10 @@ pub fn part1(input: &str) -> usize
 }
 
 
-pub fn part2(input: &str) -> usize
+pub fn part2(setup: &WarehouseSetup) -> usize
 {
-    let (warehouse, instructions) = input.split("\n\n")
-        .collect_tuple::<(&str, &str)>()
-        .unwrap_or_else(|| {
-            input.split("\r\n\r\n")
-                .collect_tuple::<(&str, &str)>()
-                .expect("Failed to split input")
-        });
-
-    let wide_warehouse = warehouse.lines()
+    let wide_warehouse = setup.warehouse.to_string()
+        .lines()
         .flat_map(|l| l.chars().chain(Some('\n')) )
         .map(|c| {
             match c {
@@ -223,28 +241,50 @@ pub fn part2(input: &str) -> usize
         .find(|c| *warehouse.get(c).unwrap() == '@' )
         .unwrap_or_else(|| panic!("Failed to locate initial position") );
 
-    let instructions = instructions.lines()
-        .flat_map(|line| line.chars() )
-        .collect::<String>();
+    trace::debug!("Before\n{warehouse}");
 
-    // Before
-    // println!("{}\n", warehouse.to_string());
-
-    instructions.chars()
-        .map(|c| dir_from_char(c).unwrap_or_else(|| panic!("Invalid direction character: {c}")))
+    setup.moves.iter()
+        .copied()
         .for_each(|dir| {
             warehouse.push_wide(&mut robot_position, dir);
-
-            // Step
-            // println!("{}\n", warehouse.to_string());
+            trace::trace!("Move {dir:?}\n{warehouse}");
         });
 
-
-    // After
-    // println!("{}", warehouse.to_string());
+    trace::debug!("After\n{warehouse}");
 
     warehouse.iter_coord()
         .filter(|&coord| *warehouse.get(&coord).unwrap() == '[' )
         .map(|c| 100 * c.0 + c.1 )
         .sum()
-}
\ No newline at end of file
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = WarehouseSetup;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day15: sample);
+
+    #[test]
+    fn test_parse_walls() {
+        assert_eq!(
+            parse("####\n#@O.\n####\n\n>>\n").err(),
+            Some(ParseError::Unexpected(2, 4, "a wall (`#`) around the warehouse".to_string(), ".".to_string()))
+        );
+        assert!(parse("####\n#@O#\n####\n\n>>\n").is_ok());
+    }
+}
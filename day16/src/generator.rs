@@ -0,0 +1,102 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Shape of the generated maze
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Size of the maze including its outer walls, rounded up to an odd number
+    pub width: usize,
+    pub height: usize,
+    /// Chance of knocking down an inner wall between two corridors, which adds alternative paths
+    pub shortcuts: f64,
+    /// Whether the end can be reached at all
+    pub reachable: bool
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 141, height: 141, shortcuts: 0.05, reachable: true }
+    }
+}
+
+/// A maze of `#` and `.`, with the start (`S`) in the bottom left corner and the end (`E`) in the top right one.
+/// Corridors are carved with a randomized depth-first search, so without shortcuts there is a single path.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let (width, height) = (options.width.max(5) | 1, options.height.max(5) | 1);
+    let mut grid = vec![vec!['#'; width]; height];
+
+    // Corridor cells are at odd coordinates, walls in between
+    let start = (height - 2, 1);
+    grid[start.0][start.1] = '.';
+    let mut stack = vec![start];
+    while let Some(&(row, col)) = stack.last() {
+        let mut neighbours = [(row.wrapping_sub(2), col), (row + 2, col), (row, col.wrapping_sub(2)), (row, col + 2)]
+            .into_iter()
+            .filter(|&(r, c)| r < height - 1 && c < width - 1 && grid[r][c] == '#')
+            .collect::<Vec<_>>();
+        neighbours.shuffle(rng);
+
+        match neighbours.first() {
+            Some(&(r, c)) => {
+                grid[(row + r) / 2][(col + c) / 2] = '.';
+                grid[r][c] = '.';
+                stack.push((r, c));
+            }
+            None => { stack.pop(); }
+        }
+    }
+
+    // Inner walls between two corridors, horizontally or vertically
+    for (row, line) in grid.iter_mut().enumerate().take(height - 1).skip(1) {
+        for (col, tile) in line.iter_mut().enumerate().take(width - 1).skip(1) {
+            let between = (row + col) % 2 == 1;
+            if *tile == '#' && between && rng.random_bool(options.shortcuts) {
+                *tile = '.';
+            }
+        }
+    }
+
+    let end = (1, width - 2);
+    if !options.reachable {
+        grid[end.0 + 1][end.1] = '#';
+        grid[end.0][end.1 - 1] = '#';
+    }
+    grid[start.0][start.1] = 'S';
+    grid[end.0][end.1] = 'E';
+
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1, part2, NoPath};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 15, height: 10, shortcuts: 0.0, reachable: true };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(16), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(16), &options));
+
+        // A single path: every tile on it is on the best path
+        let maze = parse(&input).unwrap();
+        assert!(part1(&maze).unwrap() >= 1000);
+        assert!(part2(&maze).unwrap() >= 15 - 2 + 11 - 2);
+
+        let options = Options { reachable: false, ..options };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(16), &options);
+        let end = input.find('E').unwrap();
+        assert_eq!(&input[end - 1..=end + 1], "#E#");
+
+        // Walled off: both parts tell so instead of panicking
+        let maze = parse(&input).unwrap();
+        assert_eq!(part1(&maze), Err(NoPath));
+        assert_eq!(part2(&maze), Err(NoPath));
+    }
+}
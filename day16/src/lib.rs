@@ -2,6 +2,20 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use rectangle::{Rectangle, Rectangular, RectangularData, Direction};
 
+use thiserror::Error;
+
+use parsing::{char_grid, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
+
+/// The walls keep the reindeer from ever reaching the end tile
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy)]
+#[error("There is no path from the start tile to the end tile")]
+pub struct NoPath;
+
 
 #[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
 struct State {
@@ -73,13 +87,22 @@ trait Warehouse: RectangularData<char> + Rectangular {
 impl Warehouse for Rectangle<char> {}
 
 
-pub fn part1(input: &str) -> u32
+pub fn parse(input: &str) -> Result<Rectangle<char>, ParseError>
 {
-    // Parse input
-    let maze = Rectangle::from_char_str(input)
-        .map_err( |error|  panic!("Failed to parse rectangle. Reason: {error}") )
-        .unwrap();
+    let maze = Rectangle::from_vec(char_grid(input, |c| "#.SE".contains(c), "`#`, `.`, `S` or `E`")?)?;
 
+    // The start and end tiles must be present
+    for (tile, name) in [('S', "start tile (`S`)"), ('E', "end tile (`E`)")] {
+        if !maze.iter_coord_and_data().any(|(_, &c)| c == tile) {
+            return Err(ParseError::missing(name));
+        }
+    }
+
+    Ok(maze)
+}
+
+pub fn part1(maze: &Rectangle<char>) -> Result<u32, NoPath>
+{
     // Define initial state (position and direction)
     let start_position = maze.iter_coord()
         .find(|coord| maze.is_start(coord) )
@@ -101,7 +124,7 @@ pub fn part1(input: &str) -> u32
 
     // Pop transitions until empty
     while let Some(Transition{initial_state, new_state}) = transitions_pending.pop() {
-        //println!("Popping transition: {initial_state:?} -> {new_state:?}");
+        trace::trace!("Popping transition: {initial_state:?} -> {new_state:?}");
 
         // Get the steps it took to arrive to the initial state (this must succeed)
         let initial_cost = state_map.get(&initial_state).expect("Initial state must exist");
@@ -143,7 +166,7 @@ pub fn part1(input: &str) -> u32
         .filter_map(|s: State| state_map.get(&s) )
         .copied()
         .min()
-        .unwrap()
+        .ok_or(NoPath)
 }
 
 
@@ -156,13 +179,8 @@ struct StepInfo {
 
 // TODO: Consider rewriting using petgraph
 
-pub fn part2(input: &str) -> usize
+pub fn part2(maze: &Rectangle<char>) -> Result<usize, NoPath>
 {
-    // Parse input
-    let maze = Rectangle::from_char_str(input)
-        .map_err( |error|  panic!("Failed to parse rectangle. Reason: {error}") )
-        .unwrap();
-
     // Define initial state (position and direction)
     let start_position = maze.iter_coord()
         .find(|coord| maze.is_start(coord) )
@@ -186,7 +204,7 @@ pub fn part2(input: &str) -> usize
 
     // Pop transitions until empty
     while let Some(Transition{initial_state, new_state}) = transitions_pending.pop() {
-        //println!("Popping transition: {initial_state:?} -> {new_state:?}");
+        trace::trace!("Popping transition: {initial_state:?} -> {new_state:?}");
 
         // Get the last step taken to arrive to the initial state (this must succeed)
         let initial_step_idx = *state_map.get(&initial_state).expect("Initial state must exist");
@@ -254,7 +272,7 @@ pub fn part2(input: &str) -> usize
         .map(|step_idx| steps_graph.get(*step_idx).unwrap() )
         .map(|step_info| step_info.cost )
         .min()
-        .unwrap();
+        .ok_or(NoPath)?;
 
     // Get final steps that have the min cost (there may be multiple ones)
     let best_final_steps_indices = final_step_indices.iter()
@@ -290,11 +308,31 @@ pub fn part2(input: &str) -> usize
         })
         .collect::<HashSet<(usize, usize)>>();
 
-    // Debug print statement
-    // let output = maze.to_string_with_changes(
-    //     |coord, c| if best_positions.contains(&coord) { 'O' } else { c } );
-    // println!("{output}");
+    trace::trace!("\n{}", maze.to_string_with_changes(
+        |coord, c| if best_positions.contains(&coord) { 'O' } else { c } ));
 
     // Count unique positions
-    best_positions.len()
+    Ok( best_positions.len() )
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Rectangle<char>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day16: sample, sample2);
 }
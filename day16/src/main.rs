@@ -1,40 +1,12 @@
 use std::fs;
+use std::process;
 
 use day16::*;
+use solution::Answer;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT_1: &str = "###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
-static SAMPLE_INPUT_2: &str = "#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################";
+static SAMPLE_INPUT_1: &str = include_str!("../data/sample.txt");
+static SAMPLE_INPUT_2: &str = include_str!("../data/sample2.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -54,10 +26,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let maze = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = Answer::from(part1(&maze));
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = Answer::from(part2(&maze));
     println!("Part 2, solution: {part2_solution}");
 }
 
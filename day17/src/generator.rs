@@ -0,0 +1,61 @@
+use rand::Rng;
+
+/// Shape of the generated program
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of 3-bit digits in register A, which is also the number of values output
+    pub digits: u32,
+    /// Number of instructions scrambling B and C before each output
+    pub body: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { digits: 16, body: 5 }
+    }
+}
+
+/// A program with the same loop as the puzzle inputs: starting with `bst 4`, a body of `bxl`, `cdv 5`
+/// and `bxc`, then `out`, `adv 3` and `jnz 0`. It always halts, after outputting one value per digit of A.
+/// `cdv 5` only appears while B is below 8, so that the shift never overflows.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let digits = options.digits.clamp(1, 21);
+    let a = rng.random_range(1 << (3 * (digits - 1))..1_u64 << (3 * digits));
+
+    let mut program = vec![2, 4];
+    let mut b_is_small = true;
+    for _ in 0..options.body {
+        let instruction = match rng.random_range(0..3) {
+            0 if b_is_small => [7, 5],
+            1               => { b_is_small = false; [4, rng.random_range(0..8)] },
+            _               => [1, rng.random_range(0..8)],
+        };
+        program.extend(instruction);
+    }
+    program.extend([5, rng.random_range(4..7), 0, 3, 3, 0]);
+
+    let program = program.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+    format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n")
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { digits: 12, body: 8 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(17), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(17), &options));
+
+        let cpu = parse(&input).unwrap();
+        assert_eq!(part1(&cpu).unwrap().split(',').count(), 12);
+    }
+}
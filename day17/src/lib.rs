@@ -1,9 +1,20 @@
-use regex::Regex;
 use thiserror::Error;
 use itertools::Itertools;
 
+use parsing::{comma_list, parse_number, records, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
 use u64 as Register;
 
+/// Instructions run before a program is considered stuck in a loop
+pub const MAX_STEPS: usize = 100_000;
+
+/// Instructions run by all the programs tried while looking for register A, before giving up
+pub const MAX_SEARCH_STEPS: usize = 10_000_000;
+
 #[derive(Error, Debug)]
 pub enum ComputerError {
     #[error("Cannot access instruction {position:?}, Program Length {program_length:?})")]
@@ -16,11 +27,27 @@ pub enum ComputerError {
         position: usize,
         program_length: usize,
     },
+    #[error("Combo operand 7 is reserved, found at {position:?}")]
+    ReservedOperand {
+        position: usize,
+    },
+    #[error("Value {value:?} at {position:?} is not a 3-bit number")]
+    InvalidValue {
+        position: usize,
+        value: Register,
+    },
+    #[error("Program did not halt after {0} steps")]
+    StepLimit(usize),
+    #[error("No value of register A makes the program output itself")]
+    NoQuine,
+    #[error("Gave up looking for register A after {0} steps")]
+    SearchLimit(usize),
     #[error("Undefined error")]
     Undefined,
 }
 
-struct Computer {
+#[derive(Clone, Debug)]
+pub struct Computer {
     a: Register,
     b: Register,
     c: Register,
@@ -28,6 +55,7 @@ struct Computer {
     pc: usize,
     increase_pc: bool,
     output: Vec<Register>,
+    steps: usize,
     initial_state: (Register, Register, Register)
 }
 
@@ -35,14 +63,15 @@ type ComputerResult = Result<(),ComputerError>;
 //use Result<(),ComputerError> as ComputerResult;
 
 impl Computer {
-    fn new(a: Register, b: Register, c: Register, program: Vec<Register>) -> Self {
-        Self { a, b, c, program, pc: 0, increase_pc: true, output: Vec::new(), initial_state: (a, b, c)}
+    pub fn new(a: Register, b: Register, c: Register, program: Vec<Register>) -> Self {
+        Self { a, b, c, program, pc: 0, increase_pc: true, output: Vec::new(), steps: 0, initial_state: (a, b, c)}
     }
 
     fn restart(&mut self) {
         (self.a, self.b, self.c) = self.initial_state;
         self.pc = 0;
         self.increase_pc = true;
+        self.steps = 0;
         self.output.clear()
     }
 
@@ -50,11 +79,24 @@ impl Computer {
         self.pc == self.program.len() //.try_into().expect("Failed to convert program length to Register type")
     }
 
+    /// Run until the program halts, or until it is considered stuck after `MAX_STEPS` instructions
     fn run(&mut self) -> ComputerResult {
-        while !self.finished() {
+        self.run_for(MAX_STEPS)
+    }
+
+    /// Same as run, but gives up on programs that take more than `max_steps` instructions
+    pub fn run_for(&mut self, max_steps: usize) -> ComputerResult {
+        for _ in 0..max_steps {
+            if self.finished() {
+                return Ok(());
+            }
             self.step()?;
         }
-        Ok(())
+        if self.finished() { Ok(()) } else { Err(ComputerError::StepLimit(max_steps)) }
+    }
+
+    pub fn output(&self) -> &[Register] {
+        &self.output
     }
 
     fn get_instruction(&self) -> Result<&Register, ComputerError> {
@@ -78,7 +120,8 @@ impl Computer {
             4 => Ok(self.a),
             5 => Ok(self.b),
             6 => Ok(self.c),
-            _ => unimplemented!()
+            7 => Err(ComputerError::ReservedOperand { position: self.pc + 1 }),
+            &value => Err(ComputerError::InvalidValue { position: self.pc + 1, value }),
         }
     }
 
@@ -92,7 +135,7 @@ impl Computer {
             5 => Ok(5),
             6 => Ok(6),
             7 => Ok(7),
-            _ => unimplemented!()
+            &value => Err(ComputerError::InvalidValue { position: self.pc + 1, value }),
         }
     }
 
@@ -107,10 +150,11 @@ impl Computer {
             5 => self.op_out()?,
             6 => self.op_bdv()?,
             7 => self.op_cdv()?,
-            _ => unimplemented!()
+            &value => return Err(ComputerError::InvalidValue { position: self.pc, value }),
         }
 
         // Handle increasing PC
+        self.steps += 1;
         if self.increase_pc {
             self.pc += 2;
         }
@@ -119,72 +163,64 @@ impl Computer {
         Ok( () )
     }
 
-    fn op_adv(&mut self) -> ComputerResult { self.a >>= self.combo()?; Ok(()) }
+    fn op_adv(&mut self) -> ComputerResult { self.a = shift(self.a, self.combo()?); Ok(()) }
     fn op_bxl(&mut self) -> ComputerResult { self.b ^= self.literal()?; Ok(()) }
     fn op_bst(&mut self) -> ComputerResult { self.b = self.combo()? % 8; Ok(()) }
-    fn op_jnz(&mut self) -> ComputerResult { if self.a == 0 { Ok(()) } else { self.pc = self.literal()?.try_into().unwrap(); self.increase_pc = false; Ok(() )} }
+    fn op_jnz(&mut self) -> ComputerResult { if self.a == 0 { Ok(()) } else { self.pc = self.literal()? as usize; self.increase_pc = false; Ok(() )} }
     fn op_bxc(&mut self) -> ComputerResult { self.b ^= self.c; Ok(()) }
     fn op_out(&mut self) -> ComputerResult { self.output.push(self.combo()? % 8); Ok(()) }
-    fn op_bdv(&mut self) -> ComputerResult { self.b = self.a >> self.combo()?; Ok(()) }
-    fn op_cdv(&mut self) -> ComputerResult { self.c = self.a >> self.combo()?; Ok(()) }
+    fn op_bdv(&mut self) -> ComputerResult { self.b = shift(self.a, self.combo()?); Ok(()) }
+    fn op_cdv(&mut self) -> ComputerResult { self.c = shift(self.a, self.combo()?); Ok(()) }
+}
+
+/// Division by a power of 2, which is 0 once all the bits are shifted out
+fn shift(value: Register, exponent: Register) -> Register {
+    u32::try_from(exponent).ok()
+        .and_then(|exponent| value.checked_shr(exponent))
+        .unwrap_or(0)
 }
 
-pub fn part1(input: &str) -> String
+pub fn parse(input: &str) -> Result<Computer, ParseError>
 {
-    let mut cpu = {
-        let mut a = 0;
-        let mut b = 0;
-        let mut c = 0;
-        let mut program = Vec::new();
-
-        let re = Regex::new(r"(?<key>Register [ABC]|Program): (?<data>[\d,]+)").unwrap();
-
-        for (key, data) in re.captures_iter(input).map(|caps| (caps.name("key").unwrap().as_str(), caps.name("data").unwrap().as_str()) ) {
-            match key {
-                "Register A" => a = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing A: `{data}`") ),
-                "Register B" => b = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing B: `{data}`") ),
-                "Register C" => c = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing C: `{data}`") ),
-                "Program"    => program = data.split(',').map(|n_txt| n_txt.parse::<Register>().unwrap() ).collect::<Vec<_>>(),
-                _  => panic!("Unknown input {key}"),
-            };
-        }
+    let mut a = 0;
+    let mut b = 0;
+    let mut c = 0;
+    let mut program = None;
+
+    for record in records(input) {
+        let (key, data) = record?;
+
+        match key {
+            "Register A" => a = parse_number(input, data, "a register value")?,
+            "Register B" => b = parse_number(input, data, "a register value")?,
+            "Register C" => c = parse_number(input, data, "a register value")?,
+            "Program"    => program = Some( comma_list(data)
+                .map(|n_txt| match parse_number::<Register>(input, n_txt, "a 3-bit number") {
+                    Ok(n) if n < 8 => Ok(n),
+                    _              => Err(ParseError::unexpected(input, n_txt, "a 3-bit number")),
+                })
+                .collect::<Result<Vec<_>, _>>()? ),
+            _            => return Err(ParseError::unexpected(input, key, "`Register <A|B|C>` or `Program`")),
+        };
+    }
 
-        Computer::new(a, b, c, program)
-    };
+    let program = program.ok_or_else(|| ParseError::missing("program"))?;
 
-    // Run and print if there are any errors
-    let result = cpu.run();
+    Ok( Computer::new(a, b, c, program) )
+}
 
-    // Panic if the CPU ended in a bad state
-    if let Err(error) = result {
-        panic!("Error: {error}");
-    }
+pub fn part1(cpu: &Computer) -> Result<String, ComputerError>
+{
+    let mut cpu = cpu.clone();
+    cpu.run()?;
 
-    cpu.output.iter().map(Register::to_string).join(",")
+    Ok( cpu.output.iter().map(Register::to_string).join(",") )
 }
 
-pub fn part2(input: &str) -> Register
+pub fn part2(cpu: &Computer) -> Result<Register, ComputerError>
 {
-    let mut cpu = {
-        let mut a = 0;
-        let mut b = 0;
-        let mut c = 0;
-        let mut program = Vec::new();
-
-        let re = Regex::new(r"(?<key>Register [ABC]|Program): (?<data>[\d,]+)").unwrap();
-
-        for (key, data) in re.captures_iter(input).map(|caps| (caps.name("key").unwrap().as_str(), caps.name("data").unwrap().as_str()) ) {
-            match key {
-                "Register A" => a = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing A: `{data}`") ),
-                "Register B" => b = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing B: `{data}`") ),
-                "Register C" => c = data.parse::<Register>().unwrap_or_else(|_| panic!("Error parsing C: `{data}`") ),
-                "Program"    => program = data.split(',').map(|n_txt| n_txt.parse::<Register>().unwrap() ).collect::<Vec<_>>(),
-                _  => panic!("Unknown input {key}"),
-            };
-        }
-
-        Computer::new(a, b, c, program)
-    };
+    let mut cpu = cpu.clone();
+    let mut search_steps = 0;
 
     // After looking at the MY input, here's the translation from decimal to 3-bit assembly:
     //  0: bst 4; // store in b the modulo 8 of combo(4), which is (reg_a)
@@ -233,16 +269,20 @@ pub fn part2(input: &str) -> Register
             // Calculate the full value of register A for the current test
             let mut copy_of_a = a_as_3bit_numbers.clone();
             copy_of_a.push(val);
-            let subset_of_a: Register = copy_of_a.iter()
-                .rev()
-                .enumerate()
-                .map(|(idx, val)| val * 8_u64.pow(idx as u32) )
-                .sum();
 
-            // Assign A and run the program
+            // Too many 3-bit numbers for the register is a dead-end too
+            let Some(subset_of_a) = from_3bit_numbers(&copy_of_a) else {
+                continue;
+            };
+
+            // Assign A and run the program, a program that fails or loops does not give the expected output
             cpu.restart();
             cpu.a = subset_of_a;
             let _ = cpu.run();
+            search_steps += cpu.steps;
+            if search_steps > MAX_SEARCH_STEPS {
+                return Err(ComputerError::SearchLimit(MAX_SEARCH_STEPS));
+            }
 
             // If the output does not have the expected length, this is not a valid output, so keep trying
             if cpu.output.len() != copy_of_a.len() {
@@ -262,16 +302,30 @@ pub fn part2(input: &str) -> Register
         // and start testing again from one number higher
         if !found {
             // The previous number must have led to a dead-end, backtrack
-            start_at = a_as_3bit_numbers.pop().expect("Backtracked the entire vector") + 1;
+            start_at = a_as_3bit_numbers.pop().ok_or(ComputerError::NoQuine)? + 1;
         }
     }
 
     // Return the full A register
-    a_as_3bit_numbers.iter()
-        .rev()
-        .enumerate()
-        .map(|(idx, val)| val * 8_u64.pow(idx as u32) )
-        .sum::<Register>()
+    from_3bit_numbers(&a_as_3bit_numbers).ok_or(ComputerError::NoQuine)
+}
+
+/// The register holding the 3-bit numbers, most significant first, if they fit
+fn from_3bit_numbers(numbers: &[Register]) -> Option<Register> {
+    numbers.iter().try_fold(0, |register: Register, &number| {
+        register.checked_mul(8).map(|register| register + number)
+    })
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Computer;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
 }
 
 
@@ -283,6 +337,8 @@ mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    solution::fixture_tests!(Day17: sample, sample_output);
+
     #[test]
     fn test_example_1() {
         let a = 99999;
@@ -381,4 +437,55 @@ mod tests {
         assert!(final_state.is_ok());
         assert_eq!(cpu.c,  128);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        let cpu = parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0").unwrap();
+        assert_eq!((cpu.a, cpu.b, cpu.c), (729, 0, 0));
+        assert_eq!(cpu.program, vec![0,1,5,4,3,0]);
+
+        assert_eq!(
+            parse("Register D: 1\n\nProgram: 0,1").unwrap_err(),
+            ParseError::Unexpected(1, 1, "`Register <A|B|C>` or `Program`".to_string(), "Register D".to_string())
+        );
+        assert_eq!(
+            parse("Register A: 1\n\nProgram: 0,8").unwrap_err(),
+            ParseError::Unexpected(3, 12, "a 3-bit number".to_string(), "8".to_string())
+        );
+        assert_eq!(parse("Register A: 1").unwrap_err(), ParseError::missing("program"));
+    }
+
+    #[test]
+    fn test_bad_programs() {
+        // Combo operand 7 is reserved
+        let mut cpu = Computer::new(1, 0, 0, vec![0,7]);
+        assert!(matches!(cpu.run(), Err(ComputerError::ReservedOperand { position: 1 })));
+
+        // Shifting by 64 or more bits leaves nothing
+        let mut cpu = Computer::new(Register::MAX, 100, 0, vec![0,5]);
+        assert!(cpu.run().is_ok());
+        assert_eq!(cpu.a, 0);
+
+        // An instruction without operand
+        let mut cpu = Computer::new(1, 0, 0, vec![5,4,5]);
+        assert!(matches!(cpu.run(), Err(ComputerError::CannotAccessParam { position: 3, program_length: 3 })));
+
+        // A loop that never ends
+        let mut cpu = Computer::new(1, 0, 0, vec![5,4,3,0]);
+        assert!(matches!(cpu.run_for(100), Err(ComputerError::StepLimit(100))));
+        assert_eq!(cpu.output().len(), 50);
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        let cpu = parse("Register A: 1\n\nProgram: 3,0").unwrap();
+        assert!(matches!(part1(&cpu), Err(ComputerError::StepLimit(MAX_STEPS))));
+
+        // Outputs nothing, or always the same thing
+        let cpu = parse("Register A: 1\n\nProgram: 0,3").unwrap();
+        assert!(matches!(part2(&cpu), Err(ComputerError::NoQuine)));
+        let cpu = parse("Register A: 1\n\nProgram: 5,1,0,3,3,0").unwrap();
+        assert!(matches!(part2(&cpu), Err(ComputerError::NoQuine)));
+        assert_eq!(Day17::part2(&cpu, &()).to_string(), "unsolved (No value of register A makes the program output itself)");
+    }
+}
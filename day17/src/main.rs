@@ -1,18 +1,12 @@
 use std::fs;
+use std::process;
 
 use day17::*;
+use solution::Answer;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT_1: &str = "Register A: 729
-Register B: 0
-Register C: 0
-
-Program: 0,1,5,4,3,0";
-static SAMPLE_INPUT_2: &str = "Register A: 2024
-Register B: 0
-Register C: 0
-
-Program: 0,3,5,4,3,0";
+static SAMPLE_INPUT_1: &str = include_str!("../data/sample_output.txt");
+static SAMPLE_INPUT_2: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -32,10 +26,15 @@ fn main() {
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let cpu = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = Answer::from(part1(&cpu));
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = Answer::from(part2(&cpu));
     println!("Part 2, solution: {part2_solution}");
 }
 
@@ -0,0 +1,60 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::MemorySpace;
+
+/// Shape of the generated bytes
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of falling bytes, capped by the free positions
+    pub bytes: usize,
+    /// Memory region the bytes fall in, which must also be given to the solution
+    pub space: MemorySpace
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { bytes: 3450, space: MemorySpace::default() }
+    }
+}
+
+/// Positions like `5,4`, all different and never on the start or the exit corners
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let MemorySpace { width, height, .. } = options.space;
+
+    let mut positions = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&position| position != (0, 0) && position != (width - 1, height - 1))
+        .collect::<Vec<_>>();
+    positions.shuffle(rng);
+
+    positions.iter()
+        .take(options.bytes)
+        .map(|(x, y)| format!("{x},{y}\n"))
+        .collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let space = MemorySpace { width: 7, height: 7, fall_count: 0 };
+        let options = Options { bytes: 100, space };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(18), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(18), &options));
+
+        // Every free position is filled, so some byte blocks the exit
+        let positions = parse(&input).unwrap();
+        assert_eq!(positions.len(), 7 * 7 - 2);
+        assert!(part2(&positions, 7, 7, 0).is_ok());
+    }
+}
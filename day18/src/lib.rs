@@ -1,26 +1,60 @@
 use std::collections::HashMap;
 
-use itertools::{self, Itertools};
 use rectangle::{Rectangle, RectangularData};
 use petgraph::{algo::astar, graph::UnGraph, prelude::StableUnGraph, stable_graph::NodeIndex};
+use thiserror::Error;
 
+use parsing::{exactly, parse_number, unsigned_integers, ParseError};
+use solution::{Answer, Solution};
 
-pub fn part1(input: &str, width: usize, height: usize, fall_count: usize) -> usize
+#[cfg(feature = "generator")]
+pub mod generator;
+
+
+/// Positions of the falling bytes, as (row, col)
+pub fn parse(input: &str) -> Result<Vec<(usize, usize)>, ParseError>
 {
     // Parse input
     let positions = input.lines()
         .map(|line|
         {
-            line.split(',')
-                .map(|n| n.parse::<usize>().unwrap() )
-                .collect_tuple::<(usize, usize)>()
-                .map(|t| (t.1, t.0) ) // Flip input because we use (row, col) instead of (x, y)
-                .unwrap()
+            let (x, y) = line.split_once(',')
+                .ok_or_else(|| ParseError::unexpected(input, line, "a position like `5,4`"))?;
+            // Flip input because we use (row, col) instead of (x, y)
+            Ok( (parse_number(input, y, "a coordinate")?, parse_number(input, x, "a coordinate")?) )
         })
-        .collect::<Vec<_>>();
-    //println!("{positions:?}");
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    Ok(positions)
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MemoryError {
+    #[error("Invalid byte: {0}")]
+    Position(#[from] ParseError),
+    /// The bytes fallen so far already cut the start from the exit
+    #[error("There is no path to the exit after the first {0} bytes")]
+    NoPath(usize),
+    #[error("No byte cuts the path to the exit")]
+    NeverBlocked,
+}
 
-    // Update with fallen bytes
+/// Every byte must fall inside the memory region, reported at its line of the input
+pub fn check_positions(positions: &[(usize, usize)], width: usize, height: usize) -> Result<(), ParseError>
+{
+    match positions.iter().position(|&(row, col)| row >= height || col >= width) {
+        Some(idx) => Err(ParseError::Unexpected(idx + 1, 1,
+            format!("a position inside the {width}x{height} memory space"),
+            format!("{},{}", positions[idx].1, positions[idx].0))),
+        None      => Ok(()),
+    }
+}
+
+pub fn part1(positions: &[(usize, usize)], width: usize, height: usize, fall_count: usize) -> Result<usize, MemoryError>
+{
+    check_positions(positions, width, height)?;
+
+    // Update with fallen bytes, all inside the region
     let mut region = Rectangle::from_repeated_element('.', height, width);
     positions.iter()
         .take(fall_count)
@@ -28,7 +62,7 @@ pub fn part1(input: &str, width: usize, height: usize, fall_count: usize) -> usi
     {
         region.set(coord, &'#').unwrap();
     });
-    //println!("{}", region.to_string());
+    trace::trace!("\n{region}");
 
     // Build graph
     let mut graph = UnGraph::new_undirected();
@@ -51,13 +85,15 @@ pub fn part1(input: &str, width: usize, height: usize, fall_count: usize) -> usi
         }
     }
 
-    // Locate start and end nodes
+    // Locate start and end nodes, unless a byte fell on them
     let goal_coord = (height - 1, width - 1);
-    let start_node_idx = *node_map.get(&(0, 0)).unwrap();
-    let goal_node_idx  = *node_map.get(&goal_coord).unwrap();
+    let fallen = fall_count.min(positions.len());
+    let (Some(&start_node_idx), Some(&goal_node_idx)) = (node_map.get(&(0, 0)), node_map.get(&goal_coord)) else {
+        return Err(MemoryError::NoPath(fallen));
+    };
 
     // Run the A* algorithm
-    let (cost, _path) = astar(
+    let (cost, path) = astar(
         &graph,
         start_node_idx,
         |node_idx| node_idx == goal_node_idx,
@@ -66,41 +102,24 @@ pub fn part1(input: &str, width: usize, height: usize, fall_count: usize) -> usi
             let node_coord = graph.node_weight(node_idx).unwrap();
             let delta = (height - node_coord.0, width - node_coord.1);
             delta.0 + delta.1
-        }).expect("Expected A* to work");
-
-    // Optionally print the correct path
-    // let path_coord = _path.iter()
-    //     .map(|node_idx| *graph.node_weight(*node_idx).unwrap())
-    //     .collect::<Vec<_>>();
-    // let result_string = region.to_string_with_changes(|coord, c| {
-    //     if c == '.' && path_coord.contains(&coord) {
-    //         'O'
-    //     } else {
-    //         c
-    //     }
-    // });
-    // println!("{result_string}");
-
-    cost
+        }).ok_or(MemoryError::NoPath(fallen))?;
+
+    // The path found, drawn over the region
+    trace::trace!("\n{}", {
+        let path_coord = path.iter()
+            .map(|node_idx| *graph.node_weight(*node_idx).unwrap())
+            .collect::<Vec<_>>();
+        region.to_string_with_changes(|coord, c| if c == '.' && path_coord.contains(&coord) { 'O' } else { c })
+    });
+
+    Ok(cost)
 }
 
-pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize) -> String
+pub fn part2(positions: &[(usize, usize)], width: usize, height: usize, initial_fall_count: usize) -> Result<String, MemoryError>
 {
+    check_positions(positions, width, height)?;
 
-    // Parse input
-    let positions = input.lines()
-        .map(|line|
-        {
-            line.split(',')
-                .map(|n| n.parse::<usize>().unwrap() )
-                .collect_tuple::<(usize, usize)>()
-                .map(|t| (t.1, t.0) ) // Flip input because we use (row, col) instead of (x, y)
-                .unwrap()
-        })
-        .collect::<Vec<_>>();
-    //println!("{positions:?}");
-
-    // Update with fallen bytes
+    // Update with fallen bytes, all inside the region
     let mut region = Rectangle::from_repeated_element('.', height, width);
     positions.iter()
         .take(initial_fall_count)
@@ -108,11 +127,11 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
     {
         region.set(coord, &'#').unwrap();
     });
-    //println!("{}", region.to_string());
+    trace::trace!("\n{region}");
 
     // Build graph
     let mut graph = StableUnGraph::with_capacity(width*height, width*height*4);
-    let mut node_map: HashMap<(usize, usize), NodeIndex<u16>> = HashMap::new();
+    let mut node_map: HashMap<(usize, usize), NodeIndex> = HashMap::new();
 
     // Add nodes to the graph
     for (coord, &c) in region.iter_coord_and_data() {
@@ -131,10 +150,12 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
         }
     }
 
-    // Locate start and end nodes
+    // Locate start and end nodes, unless a byte fell on them
     let goal_coord = (height - 1, width - 1);
-    let start_node_idx = *node_map.get(&(0, 0)).unwrap();
-    let goal_node_idx  = *node_map.get(&goal_coord).unwrap();
+    let fallen = initial_fall_count.min(positions.len());
+    let (Some(&start_node_idx), Some(&goal_node_idx)) = (node_map.get(&(0, 0)), node_map.get(&goal_coord)) else {
+        return Err(MemoryError::NoPath(fallen));
+    };
 
     // Run the A* algorithm initially
     let (_, mut path) = astar(
@@ -146,7 +167,7 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
             let node_coord = graph.node_weight(node_idx).unwrap();
             let delta = (height - node_coord.0, width - node_coord.1);
             delta.0 + delta.1
-        }).expect("Expected A* to work");
+        }).ok_or(MemoryError::NoPath(fallen))?;
 
     let mut path_coord = path.iter()
         .map(|node_idx| *graph.node_weight(*node_idx).unwrap())
@@ -155,9 +176,16 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
     // For each additionally fallen byte...
     for new_coord in positions.iter().skip(initial_fall_count) {
 
-        // Remove the node
-        let new_node_idx = node_map.get(new_coord).unwrap();
-        graph.remove_node(*new_node_idx);
+        // A byte on the start or the exit cuts every path
+        if *new_coord == (0, 0) || *new_coord == goal_coord {
+            return Ok( format!("{},{}", new_coord.1, new_coord.0) );
+        }
+
+        // Remove the node, unless a byte already fell there
+        let Some(&new_node_idx) = node_map.get(new_coord) else {
+            continue;
+        };
+        graph.remove_node(new_node_idx);
 
         // Only re-run A* if the fallen byte lands on the optimal path
         if path_coord.contains(new_coord) {
@@ -173,15 +201,9 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
                 });
 
             // If A* does not find a solution, the path just broke
-            if result.is_none() {
-                // Optionally update the region to show the breaking-byte (drawing only)
-                // region.set(new_coord, &'@').unwrap();
-                // println!("{}", region.to_string());
-                return format!("{},{}", new_coord.1, new_coord.0); // Print reversed because we have been used reversed coordinates
-            }
-            else {
+            if let Some(new_result) = result {
                 // Take the new path as the new optimal path
-                (_, path) = result.unwrap();
+                (_, path) = new_result;
                 path_coord = path.iter()
                     .map(|node_idx| *graph.node_weight(*node_idx).unwrap())
                     .collect::<Vec<_>>();
@@ -189,8 +211,85 @@ pub fn part2(input: &str, width: usize, height: usize, initial_fall_count: usize
                 // Update the region (drawing only)
                 region.set(new_coord, &'#').unwrap();
             }
+            else {
+                // Show the breaking byte over the region
+                trace::trace!("\n{}", region.to_string_with_changes(|coord, c| if coord == *new_coord { '@' } else { c }));
+                return Ok( format!("{},{}", new_coord.1, new_coord.0) ); // Print reversed because we have been used reversed coordinates
+            }
         }
     }
 
-    "error".to_string()
+    Err(MemoryError::NeverBlocked)
+}
+
+/// Size of the memory region and number of bytes fallen before part 1 runs
+#[derive(Clone, Copy, Debug)]
+pub struct MemorySpace {
+    pub width: usize,
+    pub height: usize,
+    pub fall_count: usize
+}
+
+impl Default for MemorySpace {
+    fn default() -> Self {
+        MemorySpace { width: 71, height: 71, fall_count: 1024 }
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<(usize, usize)>;
+    type Params = MemorySpace;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+
+    fn part1(input: &Self::Input, space: &MemorySpace) -> Answer {
+        part1(input, space.width, space.height, space.fall_count).into()
+    }
+
+    fn part2(input: &Self::Input, space: &MemorySpace) -> Answer {
+        part2(input, space.width, space.height, space.fall_count).into()
+    }
+
+    /// `<width> <height> <fall count>`, with a width and a height of at least 1
+    fn params(text: &str) -> Result<MemorySpace, ParseError> {
+        let [width, height, fall_count] = exactly(unsigned_integers(text, text), text, "`<width> <height> <fall count>`")?;
+        if width == 0 || height == 0 {
+            return Err(ParseError::unexpected(text, text, "`<width> <height> <fall count>` with a width and a height of at least 1"));
+        }
+        Ok( MemorySpace { width, height, fall_count } )
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day18: sample);
+
+    #[test]
+    fn test_errors() {
+        let positions = parse("1,0\n0,1\n3,2\n").unwrap();
+        assert_eq!(part1(&positions, 4, 3, 1), Ok(5));
+        assert_eq!(part1(&positions, 4, 3, 2), Err(MemoryError::NoPath(2)));
+        assert_eq!(part2(&positions, 3, 3, 0), Err(MemoryError::Position(
+            ParseError::Unexpected(3, 1, "a position inside the 3x3 memory space".to_string(), "3,2".to_string())
+        )));
+        assert_eq!(part2(&positions, 4, 3, 0), Ok("0,1".to_string()));
+        assert_eq!(part2(&positions[..1], 3, 3, 0), Err(MemoryError::NeverBlocked));
+
+        // Bytes on the start or the exit, and twice on the same position
+        let positions = parse("2,2\n").unwrap();
+        assert_eq!(part1(&positions, 3, 3, 1), Err(MemoryError::NoPath(1)));
+        assert_eq!(part2(&positions, 3, 3, 0), Ok("2,2".to_string()));
+        let positions = parse("1,0\n1,0\n0,1\n").unwrap();
+        assert_eq!(part2(&positions, 3, 3, 1), Ok("0,1".to_string()));
+
+        assert!(Day18::params("0 7 12").is_err());
+    }
 }
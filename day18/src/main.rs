@@ -1,33 +1,11 @@
 use std::fs;
+use std::process;
 
 use day18::*;
+use solution::Answer;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT: &str = "5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -53,10 +31,15 @@ fn main() {
             /* fall_count */ 1024)}
     };
 
-    let part1_solution = part1(&input, width, height, fall_count);
+    let positions = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = Answer::from(part1(&positions, width, height, fall_count));
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input, width, height,fall_count);
+    let part2_solution = Answer::from(part2(&positions, width, height, fall_count));
     println!("Part 2, solution: {part2_solution}");
 }
 
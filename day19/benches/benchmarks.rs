@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day19::*;
+
+static DAY_19_INPUT: &str = include_str!("../data/sample.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+
+    // The naive approach is exponential, so it is only feasible on the sample
+    let onsen = parse(DAY_19_INPUT).unwrap();
+    let towels = onsen.towels.iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("day19_combinations");
+
+    group.bench_function("naive",
+        |b| b.iter(|| onsen.combinations.iter()
+            .map(|combination| number_of_combinations(black_box(combination), &towels) )
+            .sum::<usize>()));
+
+    group.bench_function("solid",
+        |b| b.iter(|| onsen.combinations.iter()
+            .map(|combination| number_of_combinations2(black_box(combination), &towels) )
+            .sum::<usize>()));
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+use rand::Rng;
+
+/// Stripe colors
+static COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+
+/// Shape of the generated towels and designs
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub towels: usize,
+    /// Longest towel pattern
+    pub towel_length: usize,
+    pub designs: usize,
+    /// Number of towels put together to build a design
+    pub design_towels: usize,
+    /// Chance of a design not being buildable from the towels
+    pub unbuildable: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { towels: 450, towel_length: 8, designs: 400, design_towels: 12, unbuildable: 0.5 }
+    }
+}
+
+/// Towel patterns on one line, an empty line, then one design per line.
+/// There is never a single `g` towel, so the design `g` is a last resort when no other unbuildable design is found.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut towels = Vec::new();
+    while towels.len() < options.towels {
+        let length = rng.random_range(1..=options.towel_length.max(1));
+        let towel = (0..length).map(|_| COLORS[rng.random_range(0..5)]).collect::<String>();
+        if towel != "g" && !towels.contains(&towel) {
+            towels.push(towel);
+        }
+    }
+
+    let designs = (0..options.designs)
+        .map(|_| match rng.random_bool(options.unbuildable) {
+            false => (0..options.design_towels.max(1)).map(|_| towels[rng.random_range(0..towels.len())].as_str()).collect(),
+            true  => (0..100)
+                .map(|_| (0..rng.random_range(1..=options.design_towels * 4)).map(|_| COLORS[rng.random_range(0..5)]).collect())
+                .find(|design: &String| !can_build(design, &towels))
+                .unwrap_or_else(|| "g".to_string()),
+        })
+        .collect::<Vec<String>>();
+
+    format!("{}\n\n{}\n", towels.join(", "), designs.join("\n"))
+}
+
+/// Whether the design is a concatenation of towels
+fn can_build(design: &str, towels: &[String]) -> bool {
+    // buildable[i] tells whether the first i stripes can be built
+    let mut buildable = vec![false; design.len() + 1];
+    buildable[0] = true;
+    for end in 1..=design.len() {
+        buildable[end] = towels.iter()
+            .any(|towel| towel.len() <= end && buildable[end - towel.len()] && design[..end].ends_with(towel.as_str()));
+    }
+    buildable[design.len()]
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { towels: 20, towel_length: 4, designs: 30, design_towels: 5, unbuildable: 0.0 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(19), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(19), &options));
+        assert_eq!(part1(&parse(&input).unwrap()), 30);
+
+        let options = Options { unbuildable: 1.0, ..options };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(19), &options);
+        assert_eq!(part1(&parse(&input).unwrap()), 0);
+    }
+}
@@ -2,6 +2,12 @@ use std::collections::HashMap;
 use std::str;
 use itertools::Itertools;
 
+use parsing::{comma_list, split_sections, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
 
 pub fn is_buildable(target: &str, sorted_parts: &Vec<&str>) -> Vec<usize>
 {
@@ -48,27 +54,47 @@ pub fn is_buildable(target: &str, sorted_parts: &Vec<&str>) -> Vec<usize>
     part_indices
 }
 
-pub fn part1(input: &str) -> usize
+/// Available towel patterns (sorted by length) and the desired designs
+#[derive(Debug)]
+pub struct Onsen {
+    pub towels: Vec<String>,
+    pub combinations: Vec<String>
+}
+
+pub fn parse(input: &str) -> Result<Onsen, ParseError>
 {
-    let (towels, combinations) = input.split("\n\n")
-        .collect_tuple::<(&str, &str)>()
-        .unwrap_or_else(|| {
-            input.split("\r\n\r\n")
-                .collect_tuple::<(&str, &str)>()
-                .expect("Failed to split input")
-        });
-
-    let towels = towels.split(", ")
+    let (towels, combinations) = split_sections(input, "towels and designs")?;
+
+    // Towels and designs are non-empty sequences of stripe colors
+    let check_stripes = |word: &str, expected: &str| {
+        if word.is_empty() || !word.chars().all(|c| c.is_ascii_lowercase()) {
+            Err(ParseError::unexpected(input, word, expected))
+        } else {
+            Ok(word.to_string())
+        }
+    };
+
+    let towels = comma_list(towels)
         //.map(|word| word.chars().collect::<Vec<_>>() )
         .sorted_by_key(|word| (word.len(), word.to_string() ) )
-        .collect::<Vec<_>>();
-    // println!("Towels {towels:?}");
+        .map(|word| check_stripes(word, "a towel pattern"))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let combinations = combinations.lines()
-        .collect::<Vec<&str>>();
+        .map(|line| check_stripes(line, "a design"))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    combinations.iter()
-        .filter(|&&combination| is_buildable(combination, &towels) != vec![] )
+    Ok( Onsen { towels, combinations } )
+}
+
+pub fn part1(onsen: &Onsen) -> usize
+{
+    let towels = onsen.towels.iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    onsen.combinations.iter()
+        .filter(|combination| is_buildable(combination, &towels) != vec![] )
         .count()
 }
 
@@ -106,7 +132,7 @@ pub fn number_of_combinations(target: &str, sorted_parts: &Vec<&str>) -> usize
         // If we built the target, then count it as a solution but don't leave
         if remaining_length == 0 {
             found_solutions += 1;
-            //println!("Solution found: {part_indices:?}");
+            trace::trace!("Solution found: {part_indices:?}");
         }
 
         // Otherwise, pop the last index (if it exists)
@@ -151,7 +177,7 @@ pub fn number_of_combinations2(target: &str, sorted_parts: &Vec<&str>) -> usize
             let appearances = sorted_parts.iter()
                 .filter(|&&part| part == half_b )
                 .count();
-            //println!("Round {}: Found {appearances}", str::from_utf8(half_b).unwrap());
+            trace::trace!("Round {}: Found {appearances}", String::from_utf8_lossy(half_b));
             map.insert(half_b, appearances);
         }
         else {
@@ -162,7 +188,8 @@ pub fn number_of_combinations2(target: &str, sorted_parts: &Vec<&str>) -> usize
             let appearances_h2 = *map.get(half_b).unwrap();
             let combinations = appearances_h1 * appearances_h2;
 
-            //println!("Round {}: Found {combinations} -> ({appearances_h1} of {}) * ({appearances_h2} of {})", str::from_utf8(subtarget).unwrap(), str::from_utf8(half_a).unwrap(), str::from_utf8(half_b).unwrap());
+            trace::trace!("Round {}: Found {combinations} -> ({appearances_h1} of {}) * ({appearances_h2} of {})",
+                String::from_utf8_lossy(subtarget), String::from_utf8_lossy(half_a), String::from_utf8_lossy(half_b));
 
             map.entry(subtarget)
                 .and_modify(|e| *e += combinations)
@@ -185,30 +212,29 @@ pub fn number_of_combinations2(target: &str, sorted_parts: &Vec<&str>) -> usize
     *map.get(target).unwrap_or(&0)
 }
 
-pub fn part2(input: &str) -> usize
+pub fn part2(onsen: &Onsen) -> usize
 {
-    let (towels, combinations) = input.split("\n\n")
-        .collect_tuple::<(&str, &str)>()
-        .unwrap_or_else(|| {
-            input.split("\r\n\r\n")
-                .collect_tuple::<(&str, &str)>()
-                .expect("Failed to split input")
-        });
-
-    let towels = towels.split(", ")
-        //.map(|word| word.chars().collect::<Vec<_>>() )
-        .sorted_by_key(|word| (word.len(), word.to_string() ) )
+    let towels = onsen.towels.iter()
+        .map(String::as_str)
         .collect::<Vec<_>>();
 
-    let combinations = combinations.lines()
-        .collect::<Vec<&str>>();
-
-    combinations.iter()
-        .filter(|&&combination| is_buildable(combination, &towels) != vec![] )
-        .map(|&combination| number_of_combinations2(combination, &towels) )
+    onsen.combinations.iter()
+        .filter(|combination| is_buildable(combination, &towels) != vec![] )
+        .map(|combination| number_of_combinations2(combination, &towels) )
         .sum()
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Onsen;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
 
 
 #[cfg(test)]
@@ -216,6 +242,8 @@ mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    solution::fixture_tests!(Day19: sample);
+
     #[test]
     fn test_is_buildable() {
 
@@ -235,4 +263,35 @@ mod tests {
         let parts = is_buildable("asgdf", &vec!["a", "sd", "f"]);
         assert_eq!(parts, vec![]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        let onsen = parse("r, wr, b\n\nbrwrr\nbggr").unwrap();
+        assert_eq!(onsen.towels, vec!["b", "r", "wr"]);
+        assert_eq!(onsen.combinations, vec!["brwrr", "bggr"]);
+
+        assert_eq!(parse("r, wr, b\nbrwrr").unwrap_err(), ParseError::missing("empty line separating towels and designs"));
+        assert_eq!(
+            parse("r, wr, b\n\nbrwrr\nbg-gr").unwrap_err(),
+            ParseError::Unexpected(4, 1, "a design".to_string(), "bg-gr".to_string())
+        );
+    }
+
+    proptest::proptest! {
+        // The naive approach is exponential, so keep designs short
+        #[test]
+        fn test_combinations_agree(
+            towels in proptest::collection::vec("[wubrg]{1,4}", 1..12),
+            design in "[wubrg]{1,12}"
+        ) {
+            let towels = towels.iter()
+                .sorted_by_key(|towel| (towel.len(), towel.to_string()))
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+
+            let combinations = number_of_combinations2(&design, &towels);
+            proptest::prop_assert_eq!(number_of_combinations(&design, &towels), combinations);
+            proptest::prop_assert_eq!(is_buildable(&design, &towels).is_empty(), combinations == 0);
+        }
+    }
+}
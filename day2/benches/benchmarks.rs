@@ -1,35 +1,38 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use skipper::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use adaptors::IteratorExt;
+
+use day2::*;
 
 
 fn criterion_benchmark(c: &mut Criterion) {
 
-    let v2 = [7.0, 8.0, 9.0, 10.0, 11.0, 12.0];
+    // The adaptors as part 2 uses them, on every level of generated reports
+    let text = generator::generate(&mut ChaCha8Rng::seed_from_u64(1), &generator::Options::default());
+    let reports = parse(&text).unwrap();
 
-    let input_ref = &v2;
+    let input_ref = &reports;
 
     let mut group = c.benchmark_group("day2_part2");
 
-    group.bench_function("skip_nth0",
-        |b| b.iter(|| skip_nth0(black_box(input_ref), 3)));
-
-    group.bench_function("skip_nth1",
-        |b| b.iter(|| skip_nth1(black_box(input_ref), 3).collect::<Vec<&f32>>()));
+    group.bench_function("skip_nth",
+        |b| b.iter(|| black_box(input_ref).iter().map(|report| report.iter().skip_nth(report.len() / 2).count()).sum::<usize>()));
 
-    group.bench_function("skip_nth2",
-        |b| b.iter(|| skip_nth2(black_box(input_ref).iter(), 3)));
+    group.bench_function("skip_indices",
+        |b| b.iter(|| black_box(input_ref).iter().map(|report| report.iter().skip_indices([report.len() / 2]).count()).sum::<usize>()));
 
-    group.bench_function("skip_nth3",
-        |b| b.iter(|| skip_nth3(black_box(input_ref).iter(), 3)));
+    group.bench_function("leave_one_out",
+        |b| b.iter(|| black_box(input_ref).iter().map(|report| report.iter().leave_one_out().map(|it| it.count()).sum::<usize>()).sum::<usize>()));
 
-    group.bench_function("skip_nth4",
-        |b| b.iter(|| skip_nth4(black_box(input_ref).iter(), 3).collect::<Vec<&f32>>()));
+    group.bench_function("diffs",
+        |b| b.iter(|| black_box(input_ref).iter().map(|report| report.iter().diffs().sum::<i32>()).sum::<i32>()));
 
-    group.bench_function("class",
-        |b| b.iter(|| Skipper::new(black_box(input_ref).iter(), 3).collect::<Vec<&f32>>()));
+    group.bench_function("final",
+        |b| b.iter(|| day2_part2(black_box(input_ref))));
 
     group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+use rand::Rng;
+
+/// Shape of the generated reports
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub reports: usize,
+    /// Smallest and largest number of levels in a report
+    pub levels: (usize, usize),
+    /// Number of levels replaced by a bad one in each report, so that it needs that many removals
+    pub bad_levels: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { reports: 1000, levels: (5, 8), bad_levels: 1 }
+    }
+}
+
+/// Reports of levels separated by spaces. Each starts as a safe report (monotonic, steps of 1 to 3)
+/// and then up to `bad_levels` of its levels are replaced by arbitrary ones.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut output = String::new();
+
+    for _ in 0..options.reports {
+        let length = rng.random_range(options.levels.0..=options.levels.1);
+        let direction = if rng.random_bool(0.5) { 1 } else { -1 };
+
+        let mut levels = vec![rng.random_range(30..70)];
+        for _ in 1..length {
+            let last = levels[levels.len() - 1];
+            levels.push(last + direction * rng.random_range(1..=3));
+        }
+
+        for _ in 0..rng.random_range(0..=options.bad_levels) {
+            let idx = rng.random_range(0..length);
+            levels[idx] = rng.random_range(1..99);
+        }
+
+        let line = levels.iter().map(i32::to_string).collect::<Vec<_>>().join(" ");
+        output += &line;
+        output += "\n";
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{day2_part1, parse};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { reports: 100, levels: (3, 10), bad_levels: 0 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(2), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(2), &options));
+
+        // Without bad levels every report is safe
+        let reports = parse(&input).unwrap();
+        assert!(reports.iter().all(|report| (3..=10).contains(&report.len())));
+        assert_eq!(day2_part1(&reports), 100);
+    }
+}
@@ -1,16 +1,25 @@
-pub fn day2_part1(text: &str) -> usize
+use adaptors::IteratorExt;
+use parsing::{parse_list, space_list, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod safety;
+
+use safety::SafetyRules;
+
+pub fn parse(text: &str) -> Result<Vec<Vec<i32>>, ParseError>
 {
     // Get reports
-    let reports: Vec<Vec<i32>> = text.lines()
-        .map( |line|
-            line.split(' ')
-                .map( |val| val.parse::<i32>().expect("Failed to parse") )
-                .collect::<Vec<i32>>()
-        )
-        .collect();
+    text.lines()
+        .map( |line| parse_list(text, space_list(line), "a level number") )
+        .collect()
+}
 
+pub fn day2_part1(reports: &[Vec<i32>]) -> usize
+{
     // Get a list of diffs across rows
-    let diffs = reports.into_iter()
+    let diffs = reports.iter()
         .map( |report|
         {
             report.windows(2)
@@ -33,70 +42,8 @@ pub fn day2_part1(text: &str) -> usize
 }
 
 
-/******* Skip function attempts *******/
-use std::slice::Iter;
-
-pub fn skip_nth0<T>(v1: &[T], idx: usize) -> Vec<&T> {
-    v1.iter().take(idx).chain(v1.iter().skip(idx + 1)).collect::<Vec<&T>>()
-}
-
-pub fn skip_nth1<T>(v1: &[T], idx: usize) -> impl Iterator<Item = &T> {
-    v1.iter().take(idx).chain(v1.iter().skip(idx + 1))
-}
-
-pub fn skip_nth2<T>(it: Iter<'_, T>, idx: usize) -> Vec<&T> {
-    it.clone().take(idx).chain(it.skip(idx + 1)).collect::<Vec<&T>>()
-}
-
-pub fn skip_nth3<'a, T, I>(it: I , idx: usize) -> Vec<&'a T>
-where I: Iterator<Item = &'a T> + Clone {
-    it.clone().take(idx).chain(it.skip(idx + 1)).collect::<Vec<&T>>()
-}
-
-pub fn skip_nth4<'a, T, I>(it: I , idx: usize) -> impl Iterator<Item = &'a T>
-where T: 'a,
-      I: Iterator<Item = &'a T> + Clone {
-    it.clone().take(idx).chain(it.skip(idx + 1))
-}
-
-pub struct Skipper<I>
-where
-    I: Iterator,
-{
-    idx: usize,
-    skip: usize,
-    it: I,
-}
-
-impl<I> Skipper<I>
-where
-    I: Iterator,
-{
-    pub fn new(it: I, skip: usize) -> Self {
-        Self { idx: 0, skip, it }
-    }
-}
-
-impl<I> Iterator for Skipper<I>
-where
-    I: Iterator,
-{
-    type Item = I::Item;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.idx == self.skip {
-            _ = self.it.next();
-        }
-        self.idx += 1;
-        self.it.next()
-    }
-}
-/******* Skip function attempts *******/
-
 fn report_is_valid(report: &[i32]) -> bool {
-    let diff = report.windows(2)
-        .map( |window| window[1] - window[0] )
-        .collect::<Vec<i32>>();
+    let diff = report.iter().diffs().collect::<Vec<i32>>();
 
     let all_positive = diff.iter().all( |d| *d > 0);
     let all_negative = diff.iter().all( |d| *d < 0);
@@ -104,35 +51,75 @@ fn report_is_valid(report: &[i32]) -> bool {
     small_change && (all_positive || all_negative)
 }
 
-pub fn day2_part2(text: &str) -> usize
+pub fn day2_part2(reports: &[Vec<i32>]) -> usize
 {
-    // Get reports
-    let reports: Vec<Vec<i32>> = text.lines()
-        .map( |line|
-            line.split(' ')
-                .map( |val| val.parse::<i32>().expect("Failed to parse") )
-                .collect::<Vec<i32>>()
-        )
-        .collect();
-
     // Get a list of diffs across rows
-    reports.into_iter()
+    reports.iter()
         .map( |report|
         {
             // Evaluate unmodified report
-            if report_is_valid(&report) { return true; }
+            if report_is_valid(report) { return true; }
 
-            // Evaluate modified report
-            for idx in 0..report.len() {
-                // Skip idx-th item report
-                let new_report = skip_nth0(&report, idx).into_iter().copied().collect::<Vec<i32>>();
-                if report_is_valid(&new_report) { return true; }
-            }
-
-            // If none of the above is valid, the report is invalid
-            false
+            // Evaluate every report with one item skipped
+            report.iter().copied().leave_one_out()
+                .any( |new_report| report_is_valid(&new_report.collect::<Vec<i32>>()) )
         })
         //.collect::<Vec<bool>>();
         .filter(|b| *b)
         .count()
 }
+
+/// Number of reports that are safe after removing at most `max_removals` levels, in linear time
+pub fn count_safe(reports: &[Vec<i32>], max_removals: usize) -> usize {
+    let rules = SafetyRules::default();
+    reports.iter()
+        .filter( |report| rules.dampen(report, max_removals).is_some() )
+        .count()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { count_safe(input, 0).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { count_safe(input, 1).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day2: sample);
+
+    proptest::proptest! {
+        #[test]
+        fn test_dampener_agrees(
+            report in proptest::collection::vec(0..12_i32, 0..9),
+            max_removals in 0..4_usize
+        ) {
+            let reports = [report.clone()];
+            proptest::prop_assert_eq!(count_safe(&reports, 0), day2_part1(&reports));
+            proptest::prop_assert_eq!(count_safe(&reports, 1), day2_part2(&reports));
+
+            // Against every subset of removed levels
+            let rules = SafetyRules::default();
+            let fewest = (0..1_u32 << report.len())
+                .filter( |mask| rules.is_safe(&report.iter().enumerate()
+                    .filter(|(idx, _)| mask & (1 << idx) == 0)
+                    .map(|(_, &level)| level)
+                    .collect::<Vec<_>>()) )
+                .map( |mask| mask.count_ones() as usize )
+                .min()
+                .filter( |&fewest| fewest <= max_removals );
+            let removed = rules.dampen(&report, max_removals);
+            proptest::prop_assert_eq!(removed.as_ref().map(|removed| removed.len()), fewest);
+        }
+    }
+}
@@ -1,28 +1,29 @@
 //use std::env;
 use std::fs;
+use std::process;
 
 use day2::*;
 
-static _SAMPLE_INPUT: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
+static _SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 fn main() {
     // Sample input
     //let input = _SAMPLE_INPUT.to_string();
 
     // Real/File input
-    let file_path = "data/day2_part1.txt";
+    let file_path = "data/input.txt";
     let input = fs::read_to_string(file_path)
         .expect("Should have been able to read the file");
 
-    let part1_solution = day2_part1(&input);
+    let reports = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = day2_part1(&reports);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = day2_part2(&input);
+    let part2_solution = day2_part2(&reports);
     println!("Part 2, solution: {part2_solution}");
 
 }
@@ -0,0 +1,127 @@
+/// Which way the levels of a safe report must go
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Either increasing or decreasing, but the same all along the report
+    Either
+}
+
+
+// ------------ SafetyRules class ------------
+/// What makes a report safe: every step between adjacent levels goes in the same direction
+/// and changes the level by `min_step..=max_step`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SafetyRules {
+    pub min_step: i32,
+    pub max_step: i32,
+    pub direction: Direction
+}
+
+impl Default for SafetyRules {
+    /// The rules of the puzzle
+    fn default() -> Self {
+        SafetyRules { min_step: 1, max_step: 3, direction: Direction::Either }
+    }
+}
+
+impl SafetyRules {
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        self.directions().iter().any( |&increasing|
+            report.windows(2).all(|pair| self.step_is_safe(pair[0], pair[1], increasing))
+        )
+    }
+
+    /// The fewest levels to remove so that the report becomes safe (none if it already is),
+    /// or `None` if it takes more than `max_removals`. Runs in O(levels * max_removals).
+    pub fn dampen(&self, report: &[i32], max_removals: usize) -> Option<Vec<usize>> {
+        self.directions().iter()
+            .filter_map( |&increasing| self.dampen_towards(report, max_removals, increasing) )
+            .min_by_key(|removed| removed.len())
+    }
+
+    fn directions(&self) -> &'static [bool] {
+        match self.direction {
+            Direction::Increasing => &[true],
+            Direction::Decreasing => &[false],
+            Direction::Either     => &[true, false],
+        }
+    }
+
+    fn step_is_safe(&self, from: i32, to: i32, increasing: bool) -> bool {
+        let step = if increasing { to - from } else { from - to };
+        (self.min_step..=self.max_step).contains(&step)
+    }
+
+    /// Keep the longest chain of levels with safe steps between them. `removed[idx]` is the fewest levels removed
+    /// before `idx` in a chain ending at `idx`, and only the last `max_removals + 1` levels can come before it.
+    fn dampen_towards(&self, report: &[i32], max_removals: usize, increasing: bool) -> Option<Vec<usize>> {
+        if report.len() <= 1 {
+            return Some(Vec::new());
+        }
+
+        let mut removed = vec![usize::MAX; report.len()];
+        let mut previous = vec![None; report.len()];
+        for idx in 0..report.len() {
+            if idx <= max_removals {
+                removed[idx] = idx;
+            }
+            for prev in idx.saturating_sub(max_removals + 1)..idx {
+                let count = removed[prev].saturating_add(idx - prev - 1);
+                if count < removed[idx] && count <= max_removals && self.step_is_safe(report[prev], report[idx], increasing) {
+                    removed[idx] = count;
+                    previous[idx] = Some(prev);
+                }
+            }
+        }
+
+        // The chain may end early, removing the last levels too
+        let last = (report.len().saturating_sub(max_removals + 1)..report.len())
+            .filter( |&idx| removed[idx].saturating_add(report.len() - 1 - idx) <= max_removals )
+            .min_by_key( |&idx| removed[idx] + report.len() - 1 - idx )?;
+
+        let mut kept = vec![false; report.len()];
+        let mut idx = Some(last);
+        while let Some(current) = idx {
+            kept[current] = true;
+            idx = previous[current];
+        }
+        Some( (0..report.len()).filter(|&idx| !kept[idx]).collect() )
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_dampen() {
+        let rules = SafetyRules::default();
+        assert_eq!(rules.dampen(&[7, 6, 4, 2, 1], 1), Some(vec![]));
+        assert_eq!(rules.dampen(&[1, 2, 7, 8, 9], 1), None);
+        assert_eq!(rules.dampen(&[1, 5, 2, 4, 5], 1), Some(vec![1]));
+        assert_eq!(rules.dampen(&[8, 6, 4, 4, 1], 1), Some(vec![3]));
+        assert_eq!(rules.dampen(&[9, 1, 2, 3, 4], 1), Some(vec![0]));
+        assert_eq!(rules.dampen(&[1, 2, 3, 4, 9], 1), Some(vec![4]));
+        assert_eq!(rules.dampen(&[1, 9, 9, 2, 3], 1), None);
+        assert_eq!(rules.dampen(&[1, 9, 9, 2, 3], 2), Some(vec![1, 2]));
+        assert_eq!(rules.dampen(&[5], 0), Some(vec![]));
+        assert_eq!(rules.dampen(&[], 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_rules() {
+        let increasing = SafetyRules { min_step: 0, max_step: 5, direction: Direction::Increasing };
+        assert!(increasing.is_safe(&[1, 1, 6, 8]));
+        assert!(!increasing.is_safe(&[8, 6, 1, 1]));
+        assert_eq!(increasing.dampen(&[8, 6, 1, 1], 2), Some(vec![0, 1]));
+
+        let decreasing = SafetyRules { direction: Direction::Decreasing, ..SafetyRules::default() };
+        assert!(decreasing.is_safe(&[7, 6, 4, 2, 1]));
+        assert_eq!(decreasing.dampen(&[1, 3, 6, 7, 9], 3), None);
+    }
+}
@@ -0,0 +1,90 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Shape of the generated racetrack
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Size of the racetrack including its outer walls, rounded up to an odd number
+    pub width: usize,
+    pub height: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 141, height: 141 }
+    }
+}
+
+/// A single track without forks from the start (`S`) to the end (`E`), surrounded by walls.
+/// A maze is carved with a randomized depth-first search from the start, and only the way to the end is kept.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let (width, height) = (options.width.max(5) | 1, options.height.max(5) | 1);
+
+    // Corridor cells are at odd coordinates, walls in between
+    let mut cell = || (2 * rng.random_range(0..height / 2) + 1, 2 * rng.random_range(0..width / 2) + 1);
+    let start = cell();
+    let end = std::iter::repeat_with(cell).find(|&end| end != start).unwrap();
+    let mut visited = vec![vec![false; width]; height];
+    visited[start.0][start.1] = true;
+    let mut stack = vec![start];
+    let mut track = vec![start];
+    while let Some(&(row, col)) = stack.last() {
+        if (row, col) == end {
+            track = stack.clone();
+        }
+        let mut neighbours = [(row.wrapping_sub(2), col), (row + 2, col), (row, col.wrapping_sub(2)), (row, col + 2)]
+            .into_iter()
+            .filter(|&(r, c)| r < height - 1 && c < width - 1 && !visited[r][c])
+            .collect::<Vec<_>>();
+        neighbours.shuffle(rng);
+
+        match neighbours.first() {
+            Some(&(r, c)) => {
+                visited[r][c] = true;
+                stack.push((r, c));
+            }
+            None => { stack.pop(); }
+        }
+    }
+
+    let mut grid = vec![vec!['#'; width]; height];
+    for (&(r0, c0), &(r1, c1)) in track.iter().zip(track.iter().skip(1)) {
+        grid[r0][c0] = '.';
+        grid[(r0 + r1) / 2][(c0 + c1) / 2] = '.';
+    }
+    grid[start.0][start.1] = 'S';
+    grid[end.0][end.1] = 'E';
+
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use rectangle::{Rectangular, RectangularData};
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 21, height: 15 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(20), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(20), &options));
+
+        // Inner track tiles have exactly two neighbours, the start and the end only one
+        let racetrack = parse(&input).unwrap();
+        assert_eq!((racetrack.get_height(), racetrack.get_width()), (15, 21));
+        for (coord, &tile) in racetrack.iter_coord_and_data().filter(|(_, &tile)| tile != '#') {
+            let neighbours = racetrack.adjacent_coordinates(&coord).into_iter()
+                .flatten()
+                .filter(|neighbour| *racetrack.get(neighbour).unwrap() != '#')
+                .count();
+            assert_eq!(neighbours, if tile == '.' { 2 } else { 1 });
+        }
+    }
+}
@@ -3,6 +3,12 @@ use std::collections::{HashMap, HashSet};
 use rectangle::{Rectangle, Rectangular, RectangularData};
 use petgraph::{algo::dijkstra, graph::{NodeIndex, UnGraph}};
 
+use parsing::{char_grid, exactly, unsigned_integers, ParseError};
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
 type Coord = (usize, usize);
 
 fn rectangle_to_graph(rectangle: &Rectangle<char>, start_position: Coord) -> (UnGraph<Coord, ()>, HashMap<Coord, NodeIndex>){
@@ -26,7 +32,7 @@ fn rectangle_to_graph(rectangle: &Rectangle<char>, start_position: Coord) -> (Un
         let current_node_idx = *node_map.get(&coord).expect("Couldn't find coord in node map");
         processed_coords.insert(coord);
 
-        // println!("Popped coord {coord:?}");
+        trace::trace!("Popped coord {coord:?}");
 
         // Get its unprocessed adjacent cells
         for adjacent_coord in rectangle.adjacent_coordinates(&coord).into_iter()
@@ -34,7 +40,7 @@ fn rectangle_to_graph(rectangle: &Rectangle<char>, start_position: Coord) -> (Un
             .filter(|adj_coord| *rectangle.get(adj_coord).unwrap() != '#' )
             .filter( |adj_coord| !processed_coords.contains(adj_coord) )
         {
-            // println!("Processing adjacent cell {adjacent_coord:?}");
+            trace::trace!("Processing adjacent cell {adjacent_coord:?}");
 
             // If it has not been processed, it might already exist on the graph
             let new_node_idx = *node_map.entry(adjacent_coord)
@@ -51,10 +57,22 @@ fn rectangle_to_graph(rectangle: &Rectangle<char>, start_position: Coord) -> (Un
     (graph, node_map)
 }
 
-pub fn part1(input: &str) -> usize
+pub fn parse(input: &str) -> Result<Rectangle<char>, ParseError>
 {
-    let racetrack = Rectangle::from_char_str(input).unwrap();
+    let racetrack = Rectangle::from_vec(char_grid(input, |c| "#.SE".contains(c), "`#`, `.`, `S` or `E`")?)?;
+
+    // The start and end tiles must be present
+    for (tile, name) in [('S', "start tile (`S`)"), ('E', "end tile (`E`)")] {
+        if !racetrack.iter_coord_and_data().any(|(_, &c)| c == tile) {
+            return Err(ParseError::missing(name));
+        }
+    }
+
+    Ok(racetrack)
+}
 
+pub fn part1(racetrack: &Rectangle<char>, min_savings: usize) -> usize
+{
     let start_position = racetrack.iter_coord_and_data()
         .find(|(_, &c)| c == 'S' )
         .map(|(coord, _)| coord)
@@ -64,11 +82,10 @@ pub fn part1(input: &str) -> usize
         .map(|(coord, _)| coord)
         .unwrap();
 
-    // println!("{}", racetrack.to_string());
-    // println!("{start_position:?}");
-    // println!("{end_position:?}");
+    trace::trace!("\n{racetrack}");
+    trace::debug!("Start at {start_position:?}, end at {end_position:?}");
 
-    let (graph, node_map) = rectangle_to_graph(&racetrack, start_position);
+    let (graph, node_map) = rectangle_to_graph(racetrack, start_position);
 
     let start_position_idx = *node_map.get(&start_position).expect("Could not find NodeIndex for start_position");
     let end_position_idx = *node_map.get(&end_position).expect("Could not find NodeIndex for end_position");
@@ -78,7 +95,7 @@ pub fn part1(input: &str) -> usize
 
     let shortest_path_without_shortcuts = *distances_from_start.get(&end_position_idx)
         .expect("Dijkstra did not find a path from start to end");
-    // println!("Shortest distance without shortcuts: {}", shortest_path_without_shortcuts);
+    trace::info!("Shortest distance without shortcuts: {shortest_path_without_shortcuts}");
 
     let mut shortcut_map: HashMap<_, Vec<Coord>> = HashMap::new();
 
@@ -128,27 +145,25 @@ pub fn part1(input: &str) -> usize
     shortcut_list.sort_by_key(|entry| *entry.0 );
 
     // Print all the savings, like in the example (for verification)
-    // for (savings, coord_list) in shortcut_list.iter() {
-    //     println!("There are {} cheats that save {} picoseconds", coord_list.len(), *savings);
-    // }
+    for (savings, coord_list) in shortcut_list.iter() {
+        trace::debug!("There are {} cheats that save {} picoseconds", coord_list.len(), *savings);
+    }
 
-    // Return how many shortcuts save at least 100 picoseconds
+    // Return how many shortcuts save at least `min_savings` picoseconds
     shortcut_list.iter()
-        .fold( 0, |total, &(savings, shortcuts)| total + if *savings >= 100 { shortcuts.len() } else { 0 } )
+        .fold( 0, |total, &(savings, shortcuts)| total + if *savings >= min_savings { shortcuts.len() } else { 0 } )
 }
 
 /// Measure how many steps in the horizontal direction plus steps in the vertical
 /// direction it takes to go from a to b.
 fn taxicab_distance(a: &(usize, usize), b: &(usize, usize)) -> usize {
-    let delta_rows = if a.0 <= b.0 { b.0 - a.0 } else { a.0 - b.0 };
-    let delta_cols = if a.1 <= b.1 { b.1 - a.1 } else { a.1 - b.1 };
+    let delta_rows = b.0.abs_diff(a.0);
+    let delta_cols = b.1.abs_diff(a.1);
     delta_rows + delta_cols
 }
 
-pub fn part2(input: &str) -> usize
+pub fn part2(racetrack: &Rectangle<char>, min_savings: usize) -> usize
 {
-    let racetrack = Rectangle::from_char_str(input).unwrap();
-
     let start_position = racetrack.iter_coord_and_data()
         .find(|(_, &c)| c == 'S' )
         .map(|(coord, _)| coord)
@@ -158,7 +173,7 @@ pub fn part2(input: &str) -> usize
         .map(|(coord, _)| coord)
         .unwrap();
 
-    let (graph, node_map) = rectangle_to_graph(&racetrack, start_position);
+    let (graph, node_map) = rectangle_to_graph(racetrack, start_position);
 
     let start_position_idx = *node_map.get(&start_position).expect("Could not find NodeIndex for start_position");
     let end_position_idx = *node_map.get(&end_position).expect("Could not find NodeIndex for end_position");
@@ -217,20 +232,67 @@ pub fn part2(input: &str) -> usize
     shortcut_list.sort_by_key(|entry| *entry.0 );
 
     // Print all the savings, like in the example (for verification)
-    // for (savings, coord_list) in shortcut_list.iter() {
-    //     println!("There are {} cheats that save {} picoseconds", coord_list.len(), *savings);
-    // }
+    for (savings, coord_list) in shortcut_list.iter() {
+        trace::debug!("There are {} cheats that save {} picoseconds", coord_list.len(), *savings);
+    }
 
     // Info about (one of) the best solution
-    // println!("Start coordinate: {start_position:?}");
-    // println!("End   coordinate: {end_position:?}");
-    // println!("Distance between them: {}", taxicab_distance(&start_position, &end_position));
-    // let best_solution = shortcut_list.last().unwrap().1.first().unwrap();
-    // let best_entry = graph.node_weight(best_solution.0).unwrap();
-    // let best_exit = graph.node_weight(best_solution.1).unwrap();
-    // println!("Best cheat: Entry at {:?}, Exit at {:?}, Length is {}", best_entry, best_exit, taxicab_distance(best_entry, best_exit));
-
-    // Return how many shortcuts save at least 100 picoseconds
+    trace::debug!("Start at {start_position:?}, end at {end_position:?}, {} apart", taxicab_distance(&start_position, &end_position));
+    if let Some(&(entry, exit)) = shortcut_list.last().and_then(|(_, cheats)| cheats.first()) {
+        trace::debug!("Best cheat: entry at {:?}, exit at {:?}, length is {}", graph[entry], graph[exit], taxicab_distance(&graph[entry], &graph[exit]));
+    }
+
+    // Return how many shortcuts save at least `min_savings` picoseconds
     shortcut_list.iter()
-        .fold( 0, |total, &(savings, shortcuts)| total + if *savings >= 100 { shortcuts.len() } else { 0 } )
+        .fold( 0, |total, &(savings, shortcuts)| total + if *savings >= min_savings { shortcuts.len() } else { 0 } )
+}
+
+pub struct Day20;
+
+/// Cheats that count, by the picoseconds they save
+#[derive(Clone, Copy, Debug)]
+pub struct Savings {
+    pub min_savings: usize
+}
+
+impl Default for Savings {
+    fn default() -> Self {
+        Savings { min_savings: 100 }
+    }
+}
+
+impl Solution for Day20 {
+    type Input = Rectangle<char>;
+    type Params = Savings;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, savings: &Savings) -> Answer { part1(input, savings.min_savings).into() }
+    fn part2(input: &Self::Input, savings: &Savings) -> Answer { part2(input, savings.min_savings).into() }
+
+    /// `<min savings>`
+    fn params(text: &str) -> Result<Savings, ParseError> {
+        let [min_savings] = exactly(unsigned_integers(text, text), text, "`<min savings>`")?;
+        Ok( Savings { min_savings } )
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day20: sample);
+
+    #[test]
+    fn test_savings() {
+        let racetrack = parse(include_str!("../data/sample.txt")).unwrap();
+
+        // Every cheat of the puzzle example, then the ones saving 76 picoseconds or more
+        assert_eq!(part1(&racetrack, 1), 44);
+        assert_eq!(part1(&racetrack, 64), 1);
+        assert_eq!(part2(&racetrack, 76), 3);
+    }
 }
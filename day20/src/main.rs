@@ -1,23 +1,10 @@
 use std::fs;
+use std::process;
 
 use day20::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT: &str = "###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
@@ -29,16 +16,21 @@ fn main() {
     // Input choice --> CHANGE THIS ENUM HERE <--
     let input_type = InputType::File;
 
-    let input = match input_type {
-        InputType::Sample => SAMPLE_INPUT.to_string(),
-        InputType::File   => fs::read_to_string(INPUT_FILE_PATH)
-                                .expect("Should have been able to read the file")
+    let (input, min_savings) = match input_type {
+        InputType::Sample => (SAMPLE_INPUT.to_string(), 50),
+        InputType::File   => (fs::read_to_string(INPUT_FILE_PATH)
+                                .expect("Should have been able to read the file"), 100)
     };
 
-    let part1_solution = part1(&input);
+    let racetrack = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&racetrack, min_savings);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&racetrack, min_savings);
     println!("Part 2, solution: {part2_solution}");
 }
 
@@ -0,0 +1,81 @@
+use rand::Rng;
+
+/// Filler between instructions. It has no `m` nor `d`, so that it never forms an instruction by accident
+static JUNK: &[&str] = &[
+    " ", "!", "@", "#", "$", "%", "^", "&", "*", "[", "]", "{", "}", "<", ">", "?", ";", ":", "'", ",", "~", "/", "+", "-", "(", ")",
+    "why()", "where()", "select()", "how()", "what()", "who()", "when()",
+];
+
+/// Corrupted instructions that must not be counted
+static NEAR_MISSES: &[&str] = &["mul(4*", "mul(6,9!", "?(12,34)", "mul ( 2 , 4 )", "mul[3,7]", "mul(32,64]", "mul(1234,5)", "do_not_mul", "don't[]", "do[]"];
+
+/// Shape of the generated memory
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub lines: usize,
+    /// Number of pieces (instructions or filler) per line
+    pub pieces: usize,
+    /// Chance of a piece being a `mul(a,b)`
+    pub muls: f64,
+    /// Chance of a piece being a `do()` or a `don't()`
+    pub conditionals: f64,
+    /// Chance of a piece being a corrupted instruction
+    pub near_misses: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { lines: 6, pieces: 1200, muls: 0.06, conditionals: 0.005, near_misses: 0.03 }
+    }
+}
+
+/// Lines of corrupted memory mixing instructions, conditionals, near misses and filler
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut output = String::new();
+
+    for _ in 0..options.lines {
+        for _ in 0..options.pieces {
+            let roll = rng.random::<f64>();
+            if roll < options.muls {
+                output += &format!("mul({},{})", rng.random_range(1..1000), rng.random_range(1..1000));
+            }
+            else if roll < options.muls + options.conditionals {
+                output += if rng.random_bool(0.5) { "do()" } else { "don't()" };
+            }
+            else if roll < options.muls + options.conditionals + options.near_misses {
+                output += NEAR_MISSES[rng.random_range(0..NEAR_MISSES.len())];
+            }
+            else {
+                output += JUNK[rng.random_range(0..JUNK.len())];
+            }
+        }
+        output += "\n";
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{day3_part1, day3_part2, parse};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { lines: 2, pieces: 100, muls: 0.0, conditionals: 0.1, near_misses: 0.5 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(3), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(3), &options));
+        assert_eq!(day3_part1(&parse(&input).unwrap()), 0);
+
+        // Without conditionals both parts agree
+        let options = Options { conditionals: 0.0, ..Default::default() };
+        let memory = parse(&generate(&mut ChaCha8Rng::seed_from_u64(3), &options)).unwrap();
+        assert!(day3_part1(&memory) > 0);
+        assert_eq!(day3_part1(&memory), day3_part2(&memory));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::lexer::{Effect, Instruction, Lexer, NearMiss};
+
+/// How the values of the executed instructions are accumulated
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Reducer {
+    /// Stops accumulating a value which would overflow
+    Sum,
+    /// Stops accumulating a value which would overflow, like `Sum`
+    Product,
+    /// Number of values, whatever they are
+    Count
+}
+
+impl Reducer {
+    fn start(&self) -> i64 {
+        match self {
+            Reducer::Sum | Reducer::Count => 0,
+            Reducer::Product              => 1,
+        }
+    }
+
+    fn combine(&self, total: i64, value: i64) -> Option<i64> {
+        match self {
+            Reducer::Sum     => total.checked_add(value),
+            Reducer::Product => total.checked_mul(value),
+            Reducer::Count   => total.checked_add(1),
+        }
+    }
+}
+
+/// Whether an instruction changed anything when it was reached
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Outcome {
+    Executed,
+    /// A multiplication or a sum found while disabled
+    Skipped,
+    /// A value, or a total with it, beyond `i64`: the total is left as it was
+    Overflowed
+}
+
+/// An instruction of the memory as the interpreter saw it
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Step<'a> {
+    pub offset: usize,
+    pub name: &'a str,
+    pub args: Vec<i64>,
+    pub outcome: Outcome
+}
+
+/// The result of running a memory, every step that led to it, and the instructions rejected on the way
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Run<'a> {
+    pub total: i64,
+    pub trace: Vec<Step<'a>>,
+    pub near_misses: Vec<NearMiss<'a>>
+}
+
+/// Run every instruction of the table found in the memory, in order. Multiplications start enabled,
+/// and `don't()`/`do()` toggle them until the next toggle, wherever it is (ie: on another line).
+pub fn run<'a>(text: &'a str, table: &'a [Instruction], reducer: Reducer) -> Run<'a> {
+    let mut enabled = true;
+    let mut run = Run { total: reducer.start(), ..Run::default() };
+
+    for lexeme in Lexer::new(text, table) {
+        let token = match lexeme {
+            Ok(token)      => token,
+            Err(near_miss) => { run.near_misses.push(near_miss); continue; }
+        };
+
+        let outcome = match token.instruction.effect {
+            Effect::Enable  => { enabled = true;  Outcome::Executed }
+            Effect::Disable => { enabled = false; Outcome::Executed }
+            _ if !enabled   => Outcome::Skipped,
+            effect          => match value(effect, &token.args).and_then(|value| reducer.combine(run.total, value)) {
+                Some(total) => { run.total = total; Outcome::Executed }
+                None        => Outcome::Overflowed,
+            }
+        };
+        run.trace.push(Step { offset: token.offset, name: &token.instruction.name, args: token.args, outcome });
+    }
+    run
+}
+
+/// What a multiplication or a sum passes to the reducer, if it fits in `i64`
+fn value(effect: Effect, args: &[i64]) -> Option<i64> {
+    match effect {
+        Effect::Sum => args.iter().try_fold(0_i64, |total, &arg| total.checked_add(arg)),
+        _           => args.iter().try_fold(1_i64, |total, &arg| total.checked_mul(arg)),
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::borrow::Cow;
+    use crate::lexer::{Instruction, Rejection, MUL, PART1, PART2};
+
+    #[test]
+    fn test_run() {
+        let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(run(text, PART1, Reducer::Sum).total, 161);
+
+        let result = run(text, PART2, Reducer::Sum);
+        assert_eq!(result.total, 48);
+        let outcomes = result.trace.iter().map(|step| (step.offset, step.outcome)).collect::<Vec<_>>();
+        assert_eq!(outcomes, vec![
+            (1, Outcome::Executed),
+            (20, Outcome::Executed),
+            (28, Outcome::Skipped),
+            (48, Outcome::Skipped),
+            (59, Outcome::Executed),
+            (64, Outcome::Executed),
+        ]);
+        let rejections = result.near_misses.iter().map(|miss| (miss.offset, miss.rejection)).collect::<Vec<_>>();
+        assert_eq!(rejections, vec![(10, Rejection::NoOpening), (37, Rejection::NoClosing)]);
+
+        // Toggles across lines, repeated, and a trailing don't() with nothing after it
+        assert_eq!(run("don't()\nmul(2,2)don't()do()\nmul(3,3)do()mul(1,1)don't()", PART2, Reducer::Sum).total, 10);
+        assert_eq!(run("mul(2,2)don't()", PART2, Reducer::Sum).total, 4);
+    }
+
+    #[test]
+    fn test_reducers() {
+        let text = "mul(2,4)mul(3,7)don't()mul(5,5)";
+        assert_eq!(run(text, PART2, Reducer::Product).total, 8 * 21);
+        assert_eq!(run(text, PART2, Reducer::Count).total, 2);
+        assert_eq!(run(text, PART1, Reducer::Count).total, 3);
+        assert_eq!(run("", PART1, Reducer::Product).total, 1);
+
+        // The fourth product is beyond `i64`, and so is the fifth one
+        let text = "mul(999,999)".repeat(5);
+        let result = run(&text, PART1, Reducer::Product);
+        assert_eq!(result.total, 998_001_i64.pow(3));
+        let outcomes = result.trace.iter().map(|step| step.outcome).collect::<Vec<_>>();
+        assert_eq!(outcomes, vec![Outcome::Executed, Outcome::Executed, Outcome::Executed, Outcome::Overflowed, Outcome::Overflowed]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let wide_mul = Instruction { max_digits: 19, ..MUL };
+        let add = Instruction { name: Cow::Owned(String::from("add")), arity: 3, max_digits: 19, effect: Effect::Sum };
+        let table = [wide_mul, add];
+
+        let result = run("mul(9999999999,9999999999)mul(2,3)add(1,2,3)", &table, Reducer::Sum);
+        assert_eq!(result.total, 12);
+        let outcomes = result.trace.iter().map(|step| (step.name, step.outcome)).collect::<Vec<_>>();
+        assert_eq!(outcomes, vec![("mul", Outcome::Overflowed), ("mul", Outcome::Executed), ("add", Outcome::Executed)]);
+
+        // The values fit, but not their sum
+        let text = "add(9223372036854775807,0,0)add(1,0,0)";
+        let outcomes = run(text, &table, Reducer::Sum).trace.iter().map(|step| step.outcome).collect::<Vec<_>>();
+        assert_eq!(outcomes, vec![Outcome::Executed, Outcome::Overflowed]);
+        assert_eq!(run(text, &table, Reducer::Count).total, 2);
+    }
+}
@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+
+/// What an instruction does when the interpreter runs it
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Effect {
+    /// Pass the product of the arguments to the reducer, if enabled
+    Multiply,
+    /// Pass the sum of the arguments to the reducer, if enabled
+    Sum,
+    Enable,
+    Disable
+}
+
+/// An entry of the instruction table: `name(arg,...)` with exactly `arity` arguments of 1 to `max_digits` digits.
+/// Tables can hold any signature besides the ones of the puzzle (ie: `add(1,2,3)` with up to 5 digits),
+/// including ones read at runtime with an owned name.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Instruction {
+    pub name: Cow<'static, str>,
+    pub arity: usize,
+    pub max_digits: usize,
+    pub effect: Effect
+}
+
+pub const MUL:  Instruction = Instruction { name: Cow::Borrowed("mul"),   arity: 2, max_digits: 3, effect: Effect::Multiply };
+pub const DO:   Instruction = Instruction { name: Cow::Borrowed("do"),    arity: 0, max_digits: 0, effect: Effect::Enable };
+pub const DONT: Instruction = Instruction { name: Cow::Borrowed("don't"), arity: 0, max_digits: 0, effect: Effect::Disable };
+
+/// Instructions of each part of the puzzle
+pub const PART1: &[Instruction] = &[MUL];
+pub const PART2: &[Instruction] = &[MUL, DO, DONT];
+
+/// A valid instruction found in the memory, starting at byte `offset`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Token<'a> {
+    pub offset: usize,
+    pub instruction: &'a Instruction,
+    pub args: Vec<i64>
+}
+
+/// Why the name of an instruction is not followed by a valid call
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Rejection {
+    /// Something else than `(` after the name (ie: `mul[3,7]`)
+    NoOpening,
+    /// An argument without digits (ie: `mul(,5)`)
+    NoDigits,
+    /// An argument with more than `max_digits` digits (ie: `mul(1234,5)`)
+    TooManyDigits,
+    /// An argument beyond `i64`
+    TooLarge,
+    /// Something else than `,` between two arguments (ie: `mul(3 7)`)
+    NoComma,
+    /// Something else than `)` after the last argument (ie: `mul(32,64]`)
+    NoClosing
+}
+
+/// The name of an instruction at `offset`, rejected because of the byte at `position`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct NearMiss<'a> {
+    pub offset: usize,
+    pub position: usize,
+    pub instruction: &'a Instruction,
+    pub rejection: Rejection
+}
+
+
+// ------------ Lexer class ------------
+/// Iterator over the instructions of a table found in the memory, in a single pass over its bytes.
+/// The name of an instruction without a valid call after it is a near miss, anything else is corrupted and skipped.
+pub struct Lexer<'a> {
+    bytes: &'a [u8],
+    table: &'a [Instruction],
+    pos: usize
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(text: &'a str, table: &'a [Instruction]) -> Self {
+        Lexer { bytes: text.as_bytes(), table, pos: 0 }
+    }
+
+    /// The instruction starting at `pos` and its length. When none is valid, the one rejected the furthest.
+    fn token_at(&self, pos: usize) -> Option<Result<(Token<'a>, usize), NearMiss<'a>>> {
+        let rest = &self.bytes[pos..];
+        let mut near_miss: Option<NearMiss> = None;
+
+        for instruction in self.table {
+            let Some(call) = rest.strip_prefix(instruction.name.as_bytes()) else {
+                continue;
+            };
+            let start = pos + instruction.name.len();
+            let result = match call.strip_prefix(b"(") {
+                Some(args) => arguments(args, instruction)
+                    .map( |(args, len)| (args, len + 1) )
+                    .map_err( |(position, rejection)| (position + 1, rejection) ),
+                None       => Err((0, Rejection::NoOpening)),
+            };
+
+            match result {
+                Ok((args, len)) => return Some(Ok( (Token { offset: pos, instruction, args }, start + len - pos) )),
+                Err((position, rejection)) => {
+                    if near_miss.is_none_or(|miss| miss.position < start + position) {
+                        near_miss = Some(NearMiss { offset: pos, position: start + position, instruction, rejection });
+                    }
+                }
+            }
+        }
+        near_miss.map(Err)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, NearMiss<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.bytes.len() {
+            match self.token_at(self.pos) {
+                Some(Ok((token, len))) => {
+                    self.pos += len;
+                    return Some(Ok(token));
+                }
+                // Another instruction may start inside the rejected one
+                Some(Err(near_miss)) => {
+                    self.pos += 1;
+                    return Some(Err(near_miss));
+                }
+                None => self.pos += 1,
+            }
+        }
+        None
+    }
+}
+
+/// Parse the comma separated arguments and the closing parenthesis, returning them and their length,
+/// or the position of the first unexpected byte
+fn arguments(bytes: &[u8], instruction: &Instruction) -> Result<(Vec<i64>, usize), (usize, Rejection)> {
+    let mut args = Vec::with_capacity(instruction.arity);
+    let mut pos = 0;
+
+    for idx in 0..instruction.arity {
+        if idx > 0 {
+            bytes[pos..].strip_prefix(b",").ok_or((pos, Rejection::NoComma))?;
+            pos += 1;
+        }
+        let digits = bytes[pos..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err((pos, Rejection::NoDigits));
+        }
+        if digits > instruction.max_digits {
+            return Err((pos + instruction.max_digits, Rejection::TooManyDigits));
+        }
+        let arg = bytes[pos..pos + digits].iter()
+            .try_fold(0_i64, |n, byte| n.checked_mul(10)?.checked_add((byte - b'0') as i64))
+            .ok_or((pos, Rejection::TooLarge))?;
+        args.push(arg);
+        pos += digits;
+    }
+
+    bytes[pos..].strip_prefix(b")").ok_or((pos, Rejection::NoClosing))?;
+    Ok( (args, pos + 1) )
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_lexer() {
+        let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let tokens = Lexer::new(text, PART2)
+            .flatten()
+            .map( |token| (token.offset, token.instruction.name.as_ref(), token.args) )
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, vec![
+            (1, "mul", vec![2, 4]),
+            (20, "don't", vec![]),
+            (28, "mul", vec![5, 5]),
+            (48, "mul", vec![11, 8]),
+            (59, "do", vec![]),
+            (64, "mul", vec![8, 5]),
+        ]);
+
+        // Digits limits, and instructions starting inside a rejected one
+        let args = |text: &str| Lexer::new(text, PART1).flatten().map(|token| token.args).collect::<Vec<_>>();
+        assert_eq!(args("mul(1234,5)mul(,5)mul(12,3mul(4,5)"), vec![vec![4, 5]]);
+        assert_eq!(args("mul(1,2)\nmul(3,4)é"), vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_near_misses() {
+        let text = "mul[3,7]mul(32,64]mul(1234,5)mul(,5)mul(3 7)don't(x)";
+        let near_misses = Lexer::new(text, PART2)
+            .filter_map(Result::err)
+            .map( |miss| (miss.offset, miss.position, miss.instruction.name.as_ref(), miss.rejection) )
+            .collect::<Vec<_>>();
+        assert_eq!(near_misses, vec![
+            (0, 3, "mul", Rejection::NoOpening),
+            (8, 17, "mul", Rejection::NoClosing),
+            (18, 25, "mul", Rejection::TooManyDigits),
+            (29, 33, "mul", Rejection::NoDigits),
+            (36, 41, "mul", Rejection::NoComma),
+            (44, 50, "don't", Rejection::NoClosing),
+        ]);
+    }
+
+    #[test]
+    fn test_signatures() {
+        let add = Instruction { name: Cow::Owned(String::from("add")), arity: 3, max_digits: 5, effect: Effect::Sum };
+        let table = [add.clone(), MUL];
+        let tokens = Lexer::new("add(10000,2,3)mul(10000,2)add(1,2)", &table).collect::<Vec<_>>();
+        assert_eq!(tokens[0], Ok(Token { offset: 0, instruction: &add, args: vec![10000, 2, 3] }));
+        assert_eq!(tokens[1].as_ref().unwrap_err().rejection, Rejection::TooManyDigits);
+        assert_eq!(tokens[2].as_ref().unwrap_err().rejection, Rejection::NoComma);
+        assert_eq!(tokens.len(), 3);
+
+        let huge = Instruction { max_digits: 30, ..add };
+        let rejection = Lexer::new("add(99999999999999999999,1,1)", &[huge]).next().unwrap().unwrap_err().rejection;
+        assert_eq!(rejection, Rejection::TooLarge);
+    }
+}
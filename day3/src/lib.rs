@@ -1,39 +1,47 @@
-use regex::Regex;
+use parsing::ParseError;
+use solution::{Answer, Solution};
 
-pub fn day3_part1(text: &str) -> i32
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod interpreter;
+pub mod lexer;
+
+use interpreter::Reducer;
+
+// The corrupted memory is scanned as-is, so there is nothing to parse
+pub fn parse(text: &str) -> Result<String, ParseError>
+{
+    Ok(text.to_string())
+}
+
+pub fn day3_part1(text: &str) -> i64
 {
-    let re: Regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-
-    re.captures_iter(text)
-        .map( |c|
-        {
-            (c.get(1).map( |m| m.as_str().parse::<i32>().unwrap() ).unwrap(),
-             c.get(2).map( |m| m.as_str().parse::<i32>().unwrap() ).unwrap())
-        })
-        .map( |(a, b)| a * b )
-        .sum()
+    interpreter::run(text, lexer::PART1, Reducer::Sum).total
 }
 
-pub fn day3_part2(text: &str) -> i32
+pub fn day3_part2(text: &str) -> i64
 {
-    // Combine lines
-    let text: String = text.lines().fold( String::new(), |acc, e| acc + e );
-
-    // Remove dont't()...do() sections
-    let dont_do_re = Regex::new(r"(don't\(\).+?do\(\))").unwrap();
-    let text = dont_do_re.replace_all(&text, "").to_string();
-
-    // Remove trailing don't().. section if any
-    let trailing_dont_re = Regex::new(r"(don't\(\).+$)").unwrap();
-    let text = trailing_dont_re.replace_all(&text, "").to_string();
-
-    let re: Regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    re.captures_iter(&text)
-        .map( |c|
-        {
-            (c.get(1).map( |m| m.as_str().parse::<i32>().unwrap() ).unwrap(),
-             c.get(2).map( |m| m.as_str().parse::<i32>().unwrap() ).unwrap())
-        })
-        .map( |(a, b)| a * b )
-        .sum()
-}
\ No newline at end of file
+    interpreter::run(text, lexer::PART2, Reducer::Sum).total
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { day3_part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { day3_part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day3: sample, sample_mul);
+}
@@ -2,8 +2,8 @@ use std::fs;
 
 use day3::*;
 
-static _SAMPLE_INPUT_1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-static _SAMPLE_INPUT_2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+static _SAMPLE_INPUT_1: &str = include_str!("../data/sample_mul.txt");
+static _SAMPLE_INPUT_2: &str = include_str!("../data/sample.txt");
 
 fn main() {
     // Sample input
@@ -11,7 +11,7 @@ fn main() {
     //let input = _SAMPLE_INPUT_2.to_string();
 
     // Real/File input
-    let file_path = "data/day3_part1.txt";
+    let file_path = "data/input.txt";
     let input = fs::read_to_string(file_path)
         .expect("Should have been able to read the file");
 
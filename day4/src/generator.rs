@@ -0,0 +1,72 @@
+use rand::Rng;
+
+/// Shape of the generated word search
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Letters used to fill the grid
+    pub letters: String,
+    /// Number of `XMAS` planted in random directions, on top of the ones that appear by chance
+    pub planted: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 140, height: 140, letters: "XMAS".to_string(), planted: 200 }
+    }
+}
+
+/// A grid of letters, one row per line
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let letters = options.letters.chars().collect::<Vec<_>>();
+    let mut grid = (0..options.height)
+        .map(|_| (0..options.width).map(|_| letters[rng.random_range(0..letters.len())]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let word = ['X', 'M', 'A', 'S'];
+    for _ in 0..options.planted {
+        let (dr, dc) = loop {
+            let direction = (rng.random_range(-1..=1_i32), rng.random_range(-1..=1_i32));
+            if direction != (0, 0) {
+                break direction;
+            }
+        };
+        // Only plant it if it fits
+        let row = rng.random_range(0..options.height as i32);
+        let col = rng.random_range(0..options.width as i32);
+        let end = (row + 3 * dr, col + 3 * dc);
+        if end.0 < 0 || end.1 < 0 || end.0 >= options.height as i32 || end.1 >= options.width as i32 {
+            continue;
+        }
+        for (step, &ch) in word.iter().enumerate() {
+            grid[(row + step as i32 * dr) as usize][(col + step as i32 * dc) as usize] = ch;
+        }
+    }
+
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use rectangle::Rectangular;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 30, height: 20, letters: "MA".to_string(), planted: 1 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(4), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(4), &options));
+
+        let grid = parse(&input).unwrap();
+        assert_eq!((grid.get_height(), grid.get_width()), (20, 30));
+        assert!(part1(&grid) <= 1);
+    }
+}
@@ -1,121 +1,46 @@
-pub fn part1(input: &str) -> usize
-{
-    let char_table = input.lines()
-        .map( |line|  line.chars().collect::<Vec<char>>() )
-        .collect::<Vec<Vec<char>>>();
-
-    let list_of_stars = char_table.iter()
-        .enumerate()
-        .flat_map( |(line_idx, line)|
-        {
-            line.iter()
-                .enumerate()
-                .map( move |(col_idx, ch)| (line_idx, col_idx, ch) )
-        })
-        .map( |(line_idx , col_idx, ch)|
-        {
-            match *ch {
-                'X' => get_star(&char_table, line_idx, col_idx),
-                _   => vec!()
-            }
-        })
-        .collect::<Vec<Vec<String>>>();
-
-    list_of_stars.iter()
-        .flat_map( |string_list| string_list.iter() )
-        .filter( |txt| *txt == "XMAS" )
-        .count()
-}
+use parsing::{char_grid, ParseError};
+use rectangle::Rectangle;
+use solution::{Answer, Solution};
 
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod search;
 
-fn get_star(char_map: &Vec<Vec<char>>, ridx: usize, cidx: usize) -> Vec<String> {
-    let mut list = vec!{};
+use search::{Cross, Heading, WordSearch};
 
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx, -1, -1));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx, -1,  0));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx, -1,  1));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx,  0, -1));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx,  0,  1));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx,  1, -1));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx,  1,  0));
-    list.push(get_characters_in_direction(char_map, 4, ridx, cidx,  1,  1));
-
-    list.into_iter()
-        .filter( |o| o.is_some() )
-        .map( |o| o.unwrap() )
-        .collect()
+pub fn parse(input: &str) -> Result<Rectangle<char>, ParseError>
+{
+    Ok( Rectangle::from_vec(char_grid(input, |ch| ch.is_ascii_uppercase(), "an uppercase letter")?)? )
 }
 
-fn get_characters_in_direction(char_map: &Vec<Vec<char>>, nchars: usize, row: usize, col: usize, dr: i32, dc: i32) -> Option<String> {
-    let height = char_map.len();
-    let width = char_map[0].len();
-
-    if  !(0..height).contains(&row) ||
-        !(0..width ).contains(&col) ||
-        !(0..height as i32).contains(&((row as i32) + ((nchars - 1) as i32) * dr)) ||
-        !(0..width  as i32).contains(&((col as i32) + ((nchars - 1) as i32) * dc))
-    {
-        return None;
-    }
-
-    let mut result = Vec::with_capacity(nchars);
-
-    for idx in 0..nchars {
-        result.push(char_map[((row as i32) + (idx as i32) * dr) as usize][((col as i32) + (idx as i32) * dc) as usize])
-    }
-    Some(result.into_iter().collect::<String>())
+pub fn part1(grid: &Rectangle<char>) -> usize
+{
+    WordSearch::new(&["XMAS"]).find(grid, &Heading::ALL).len()
 }
 
-pub fn part2(input: &str) -> usize
+pub fn part2(grid: &Rectangle<char>) -> usize
 {
-    let char_table = input.lines()
-        .map( |line|  line.chars().collect::<Vec<char>>() )
-        .collect::<Vec<Vec<char>>>();
+    Cross::x_mas().find(grid).len()
+}
+
+pub struct Day4;
 
-    let list_of_diagonals = char_table.iter()
-        .enumerate()
-        .flat_map( |(line_idx, line)|
-        {
-            line.iter()
-                .enumerate()
-                .map( move |(col_idx, ch)| (line_idx, col_idx, ch) )
-        })
-        .map( |(line_idx , col_idx, ch)|
-        {
-            match *ch {
-                'A' => get_diagonals(&char_table, line_idx, col_idx),
-                _   => vec!()
-            }
-        })
-        .collect::<Vec<Vec<String>>>();
+impl Solution for Day4 {
+    type Input = Rectangle<char>;
+    type Params = ();
 
-    // Check how many locations have 2 diagonals that contain "MAS"
-    list_of_diagonals.iter()
-        .map( |string_list|
-        {
-            string_list.iter()
-                .filter( |txt| *txt == "MAS" )
-                .count() == 2
-        })
-        .filter( |b| *b)
-        .count()
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
 }
 
-fn get_diagonals(char_map: &Vec<Vec<char>>, ridx: usize, cidx: usize) -> Vec<String> {
-    let mut list = vec!{};
 
-    // Prevent usize underflow by returning an empty vector
-    if ridx == 0 || cidx == 0 {
-        return vec!();
-    }
 
-    list.push(get_characters_in_direction(char_map, 3, ridx + 1, cidx + 1, -1, -1));
-    list.push(get_characters_in_direction(char_map, 3, ridx + 1, cidx - 1, -1,  1));
-    list.push(get_characters_in_direction(char_map, 3, ridx - 1, cidx + 1,  1, -1));
-    list.push(get_characters_in_direction(char_map, 3, ridx - 1, cidx - 1,  1,  1));
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
 
-    list.into_iter()
-        .filter( |o| o.is_some() )
-        .map( |o| o.unwrap() )
-        .collect()
-}
\ No newline at end of file
+    solution::fixture_tests!(Day4: sample);
+}
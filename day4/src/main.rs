@@ -1,40 +1,37 @@
 use std::fs;
+use std::process;
 
 use day4::*;
 
 static INPUT_FILE_PATH: &str = "data/input.txt";
-static SAMPLE_INPUT: &str = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
+static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
 
 #[allow(dead_code)]
 enum InputType{
-    SAMPLE,
-    FILE
+    Sample,
+    File
 }
 
 fn main() {
     // Input choice --> CHANGE THIS ENUM HERE <--
-    let input_type = InputType::FILE;
+    let input_type = InputType::File;
 
 
     let input = match input_type {
-        InputType::SAMPLE => SAMPLE_INPUT.to_string(),
-        InputType::FILE   => fs::read_to_string(INPUT_FILE_PATH)
+        InputType::Sample => SAMPLE_INPUT.to_string(),
+        InputType::File   => fs::read_to_string(INPUT_FILE_PATH)
                                 .expect("Should have been able to read the file")
     };
 
-    let part1_solution = part1(&input);
+    let grid = parse(&input).unwrap_or_else(|error| {
+        eprintln!("Failed to parse input. Reason: {error}");
+        process::exit(1);
+    });
+
+    let part1_solution = part1(&grid);
     println!("Part 1, solution: {part1_solution}");
 
-    let part2_solution = part2(&input);
+    let part2_solution = part2(&grid);
     println!("Part 2, solution: {part2_solution}");
 
 }
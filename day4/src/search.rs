@@ -0,0 +1,212 @@
+use rectangle::{Rectangle, Rectangular, RectangularData};
+
+/// The eight ways a word can be read in the grid
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest
+}
+
+impl Heading {
+    pub const ALL: [Heading; 8] = [
+        Heading::North, Heading::NorthEast, Heading::East, Heading::SouthEast,
+        Heading::South, Heading::SouthWest, Heading::West, Heading::NorthWest
+    ];
+    pub const ORTHOGONAL: [Heading; 4] = [Heading::North, Heading::East, Heading::South, Heading::West];
+    pub const DIAGONAL: [Heading; 4] = [Heading::NorthEast, Heading::SouthEast, Heading::SouthWest, Heading::NorthWest];
+
+    /// Change of (row, column) of a single step
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Heading::North     => (-1,  0),
+            Heading::NorthEast => (-1,  1),
+            Heading::East      => ( 0,  1),
+            Heading::SouthEast => ( 1,  1),
+            Heading::South     => ( 1,  0),
+            Heading::SouthWest => ( 1, -1),
+            Heading::West      => ( 0, -1),
+            Heading::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn reverse(&self) -> Heading {
+        match self {
+            Heading::North     => Heading::South,
+            Heading::NorthEast => Heading::SouthWest,
+            Heading::East      => Heading::West,
+            Heading::SouthEast => Heading::NorthWest,
+            Heading::South     => Heading::North,
+            Heading::SouthWest => Heading::NorthEast,
+            Heading::West      => Heading::East,
+            Heading::NorthWest => Heading::SouthEast,
+        }
+    }
+
+    /// The coordinates `steps` steps away, if they are in the grid
+    fn walk(&self, grid: &Rectangle<char>, coord: (usize, usize), steps: isize) -> Option<(usize, usize)> {
+        let (dr, dc) = self.delta();
+        let next = (coord.0.checked_add_signed(dr * steps)?, coord.1.checked_add_signed(dc * steps)?);
+        grid.contains(&next).then_some(next)
+    }
+}
+
+/// A word of the list, read from `start` towards `heading`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Match {
+    pub word: usize,
+    pub start: (usize, usize),
+    pub heading: Heading
+}
+
+
+// ------------ WordSearch class ------------
+/// Searches many words at once: the words are stored in a trie, so each walk from a cell
+/// follows every word sharing the letters read so far, and stops as soon as none does.
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    nodes: Vec<Node>
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(char, usize)>,
+    /// Index of the word ending here, in the list given to `new` (the first one if repeated)
+    word: Option<usize>
+}
+
+impl WordSearch {
+    pub fn new(words: &[&str]) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (idx, word) in words.iter().enumerate() {
+            let mut node = 0;
+            for letter in word.chars() {
+                node = match nodes[node].children.iter().find(|(ch, _)| *ch == letter) {
+                    Some(&(_, child)) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.push((letter, child));
+                        child
+                    }
+                };
+            }
+            nodes[node].word.get_or_insert(idx);
+        }
+        WordSearch { nodes }
+    }
+
+    /// Every word of the list read in one of the `headings`, by start coordinate then heading (in the given order).
+    /// Empty words are never found.
+    pub fn find(&self, grid: &Rectangle<char>, headings: &[Heading]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for start in grid.iter_coord() {
+            for &heading in headings {
+                let mut node = 0;
+                let mut coord = Some(start);
+                while let Some(current) = coord {
+                    let letter = *grid.get(&current).unwrap();
+                    let Some(&(_, child)) = self.nodes[node].children.iter().find(|(ch, _)| *ch == letter) else {
+                        break;
+                    };
+                    node = child;
+                    if let Some(word) = self.nodes[node].word {
+                        matches.push(Match { word, start, heading });
+                    }
+                    coord = heading.walk(grid, current, 1);
+                }
+            }
+        }
+        matches
+    }
+}
+
+
+// ------------ Shapes ------------
+/// Lines through a cell which must all read `word` (forwards or backwards) with the cell as its middle letter.
+/// The X-MAS of the puzzle is `MAS` on both diagonals, and a `+` of `MAS` is `MAS` on the orthogonal lines.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Cross<'a> {
+    pub word: &'a str,
+    /// One heading per line, the opposite heading gives the same line
+    pub lines: Vec<Heading>
+}
+
+impl Cross<'_> {
+    /// The puzzle shape: `MAS` twice, in the shape of an X
+    pub fn x_mas() -> Self {
+        Cross { word: "MAS", lines: vec![Heading::SouthEast, Heading::NorthEast] }
+    }
+
+    /// Coordinates of every middle cell of the shape, in reading order
+    pub fn find(&self, grid: &Rectangle<char>) -> Vec<(usize, usize)> {
+        let letters = self.word.chars().collect::<Vec<_>>();
+        let middle = (letters.len() / 2) as isize;
+
+        let reads = |start: Option<(usize, usize)>, heading: Heading| start.is_some_and( |start|
+            letters.iter().enumerate().all( |(idx, letter)|
+                heading.walk(grid, start, idx as isize)
+                    .is_some_and(|coord| grid.get(&coord).unwrap() == letter)
+            )
+        );
+
+        grid.iter_coord()
+            .filter( |&coord| !letters.is_empty() && self.lines.iter().all( |&heading|
+                reads(heading.walk(grid, coord, -middle), heading) ||
+                reads(heading.walk(grid, coord, middle), heading.reverse())
+            ))
+            .collect()
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn grid(text: &str) -> Rectangle<char> {
+        Rectangle::from_char_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = grid("CATS\nOAXA\nTACO\n");
+        let search = WordSearch::new(&["CAT", "CATS", "TACO", "AXA", "Z", ""]);
+        assert_eq!(search.find(&grid, &[Heading::East, Heading::South]), vec![
+            Match { word: 0, start: (0, 0), heading: Heading::East },
+            Match { word: 1, start: (0, 0), heading: Heading::East },
+            Match { word: 3, start: (1, 1), heading: Heading::East },
+            Match { word: 2, start: (2, 0), heading: Heading::East },
+        ]);
+
+        let cat_anywhere = WordSearch::new(&["CAT"]).find(&grid, &Heading::ALL);
+        assert_eq!(cat_anywhere.iter().map(|m| (m.start, m.heading)).collect::<Vec<_>>(), vec![
+            ((0, 0), Heading::East),
+            ((2, 2), Heading::West),
+        ]);
+
+        // A single letter is found once per heading
+        assert_eq!(WordSearch::new(&["X"]).find(&grid, &Heading::DIAGONAL).len(), 4);
+    }
+
+    #[test]
+    fn test_cross() {
+        let x_mas = grid("M.S\n.A.\nM.S\n");
+        assert_eq!(Cross::x_mas().find(&x_mas), vec![(1, 1)]);
+
+        let plus = grid(".M.\nSAM\n.S.\n");
+        assert_eq!(Cross::x_mas().find(&plus), vec![]);
+        assert_eq!(Cross { word: "MAS", lines: Heading::ORTHOGONAL[..2].to_vec() }.find(&plus), vec![(1, 1)]);
+
+        // The middle of an even word is the first letter of its second half
+        assert_eq!(Cross { word: "AB", lines: vec![Heading::East] }.find(&grid("AB\n")), vec![(0, 1)]);
+    }
+}
@@ -0,0 +1,81 @@
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+/// Shape of the generated print queue
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Number of distinct page numbers, from 10 up
+    pub pages: usize,
+    /// Fraction of the page pairs that get a rule. Rules always follow a hidden order, so they never form a cycle
+    pub rule_density: f64,
+    pub updates: usize,
+    /// Smallest and largest number of pages in an update, odd numbers are used so that there is a middle page
+    pub update_length: (usize, usize),
+    /// Chance of an update being already in order
+    pub ordered: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { pages: 49, rule_density: 1.0, updates: 200, update_length: (5, 23), ordered: 0.5 }
+    }
+}
+
+/// Rules like `47|53`, an empty line, then updates like `75,47,61,53,29`
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut order = (10..10 + options.pages as i32).collect::<Vec<_>>();
+    order.shuffle(rng);
+
+    let mut rules = Vec::new();
+    for (i, before) in order.iter().enumerate() {
+        for after in &order[i + 1..] {
+            if rng.random_bool(options.rule_density) {
+                rules.push((before, after));
+            }
+        }
+    }
+    rules.shuffle(rng);
+
+    let mut output = rules.iter().map(|(before, after)| format!("{before}|{after}\n")).collect::<String>();
+    output += "\n";
+
+    for _ in 0..options.updates {
+        let longest = options.update_length.1.min(options.pages);
+        let length = rng.random_range(options.update_length.0.min(longest)..=longest);
+        let length = if length % 2 == 0 { length - 1 } else { length };
+        let mut update = order.choose_multiple(rng, length).copied().collect::<Vec<_>>();
+        match rng.random_bool(options.ordered) {
+            true  => update.sort_by_key(|page| order.iter().position(|p| p == page)),
+            false => update.shuffle(rng),
+        }
+        output += &update.iter().map(i32::to_string).collect::<Vec<_>>().join(",");
+        output += "\n";
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { pages: 15, rule_density: 1.0, updates: 20, update_length: (3, 9), ordered: 1.0 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(5), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(5), &options));
+
+        let queue = parse(&input).unwrap();
+        assert_eq!(queue.rules.len(), 15 * 14 / 2);
+        assert_eq!(queue.pages.len(), 20);
+        assert!(queue.pages.iter().all(|update| update.len() % 2 == 1));
+        // Everything is in order, so there is nothing to fix
+        assert_eq!(part2(&queue), Ok(0));
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum OrderError {
+    /// Each page must be printed before the next one, and the last one before the first one
+    #[error("Pages {} form a cycle of rules", cycle_text(.0))]
+    Cycle(Vec<i32>),
+    #[error("Page {0} appears more than once in the update")]
+    DuplicatePage(i32),
+}
+
+fn cycle_text(cycle: &[i32]) -> String {
+    cycle.iter().chain(cycle.first())
+        .map(i32::to_string)
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+
+// ------------ PrecedenceGraph class ------------
+/// The `a|b` rules as a graph with an edge from `a` to `b`, so that checking or ordering an update
+/// only looks at the rules about its own pages. Updates with a repeated page are rejected.
+#[derive(Debug, Clone, Default)]
+pub struct PrecedenceGraph {
+    after: HashMap<i32, Vec<i32>>
+}
+
+impl PrecedenceGraph {
+    pub fn new(rules: &[(i32, i32)]) -> Self {
+        let mut after: HashMap<i32, Vec<i32>> = HashMap::new();
+        for &(before, page) in rules {
+            after.entry(before).or_default().push(page);
+        }
+        PrecedenceGraph { after }
+    }
+
+    /// Pages that must be printed after `page`
+    pub fn after(&self, page: i32) -> &[i32] {
+        self.after.get(&page).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first rule `(before, after)` broken by the update. Every rule whose `before` is one of its pages is looked
+    /// at, even when `after` is not in the update, so this is O(pages + rules starting at its pages)
+    pub fn first_violation(&self, update: &[i32]) -> Result<Option<(i32, i32)>, OrderError> {
+        let positions = positions(update)?;
+        Ok( update.iter().enumerate().find_map( |(idx, &page)|
+            self.after(page).iter()
+                .find( |after| positions.get(after).is_some_and(|&other| other < idx) )
+                .map( |&after| (page, after) )
+        ))
+    }
+
+    pub fn is_ordered(&self, update: &[i32]) -> Result<bool, OrderError> {
+        Ok( self.first_violation(update)?.is_none() )
+    }
+
+    /// The update sorted to follow the rules (a topological sort of its pages),
+    /// or a cycle of the rules about its pages
+    pub fn order(&self, update: &[i32]) -> Result<Vec<i32>, OrderError> {
+        let positions = positions(update)?;
+        let mut successors = vec![Vec::new(); update.len()];
+        let mut predecessors = vec![Vec::new(); update.len()];
+        for (idx, &page) in update.iter().enumerate() {
+            for after in self.after(page) {
+                if let Some(&next) = positions.get(after) {
+                    successors[idx].push(next);
+                    predecessors[next].push(idx);
+                }
+            }
+        }
+
+        let mut waiting = predecessors.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut ready = (0..update.len()).filter(|&idx| waiting[idx] == 0).collect::<VecDeque<_>>();
+        let mut ordered = Vec::with_capacity(update.len());
+        while let Some(idx) = ready.pop_front() {
+            ordered.push(update[idx]);
+            for &next in &successors[idx] {
+                waiting[next] -= 1;
+                if waiting[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        match ordered.len() == update.len() {
+            true  => Ok(ordered),
+            false => Err(OrderError::Cycle(find_cycle(update, &predecessors, &waiting))),
+        }
+    }
+}
+
+
+
+// ------------ Rule analysis ------------
+impl PrecedenceGraph {
+    /// Every rule, sorted
+    pub fn rules(&self) -> Vec<(i32, i32)> {
+        let mut rules = self.after.iter()
+            .flat_map( |(&before, pages)| pages.iter().map(move |&page| (before, page)) )
+            .collect::<Vec<_>>();
+        rules.sort_unstable();
+        rules
+    }
+
+    /// Every page mentioned by a rule, sorted
+    pub fn pages(&self) -> Vec<i32> {
+        let mut pages = self.rules().into_iter().flat_map(|(before, after)| [before, after]).collect::<Vec<_>>();
+        pages.sort_unstable();
+        pages.dedup();
+        pages
+    }
+
+    /// Rules implied by the other ones through transitivity (ie: `a|c` given `a|b` and `b|c`), and the extra
+    /// copies of a repeated rule, so that removing them all keeps the same order.
+    /// Rules with a cycle are rejected: rules of a cycle each imply one another, but cannot all be removed.
+    pub fn redundant_rules(&self) -> Result<Vec<(i32, i32)>, OrderError> {
+        self.order(&self.pages())?;
+        let rules = self.rules();
+        let repeated = |idx: usize| idx > 0 && rules[idx - 1] == rules[idx];
+        Ok( rules.iter()
+            .enumerate()
+            .filter( |&(idx, &rule)| repeated(idx) || self.reaches_without(rule) )
+            .map( |(_, &rule)| rule )
+            .collect() )
+    }
+
+    /// The fewest rules that order the pages exactly like all of them (the transitive reduction).
+    /// Rules with a cycle are rejected, as they have no single smallest set.
+    pub fn minimal_rules(&self) -> Result<Vec<(i32, i32)>, OrderError> {
+        self.order(&self.pages())?;
+        let mut rules = self.rules();
+        rules.dedup();
+        let mut minimal = PrecedenceGraph::new(&rules);
+
+        for rule in rules {
+            if minimal.reaches_without(rule) {
+                minimal.after.get_mut(&rule.0).unwrap().retain(|&page| page != rule.1);
+            }
+        }
+        Ok( minimal.rules() )
+    }
+
+    /// The order of every page mentioned by the rules, if they compare any two of them (a total order)
+    pub fn total_order(&self) -> Option<Vec<i32>> {
+        let order = self.order(&self.pages()).ok()?;
+        order.windows(2)
+            .all( |pair| self.after(pair[0]).contains(&pair[1]) )
+            .then_some(order)
+    }
+
+    /// The graph in Graphviz DOT format, with its rules sorted (ie: `dot -Tsvg rules.dot`)
+    pub fn to_dot(&self) -> String {
+        let edges = self.rules().into_iter()
+            .map( |(before, after)| format!("    {before} -> {after};\n") )
+            .collect::<String>();
+        format!("digraph rules {{\n{edges}}}\n")
+    }
+
+    /// Whether a rule is implied by a path of other rules
+    fn reaches_without(&self, (from, to): (i32, i32)) -> bool {
+        let mut seen = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(page) = queue.pop_front() {
+            for &next in self.after(page) {
+                if (page, next) == (from, to) {
+                    continue;
+                }
+                if next == to {
+                    return true;
+                }
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        false
+    }
+}
+
+fn positions(update: &[i32]) -> Result<HashMap<i32, usize>, OrderError> {
+    let mut positions = HashMap::with_capacity(update.len());
+    for (idx, &page) in update.iter().enumerate() {
+        if positions.insert(page, idx).is_some() {
+            return Err(OrderError::DuplicatePage(page));
+        }
+    }
+    Ok(positions)
+}
+
+/// Pages left out of the topological sort all wait for another one left out. Going from page
+/// to waited page must come back to a page already seen, which closes a cycle.
+fn find_cycle(update: &[i32], predecessors: &[Vec<usize>], waiting: &[usize]) -> Vec<i32> {
+    let mut seen = vec![None; update.len()];
+    let mut path = Vec::new();
+    let mut idx = (0..update.len()).find(|&idx| waiting[idx] > 0).unwrap();
+
+    while seen[idx].is_none() {
+        seen[idx] = Some(path.len());
+        path.push(idx);
+        idx = *predecessors[idx].iter().find(|&&before| waiting[before] > 0).unwrap();
+    }
+
+    // The path goes backwards, from each page to one printed before it
+    path[seen[idx].unwrap()..].iter().rev().map(|&idx| update[idx]).collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+
+    static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+
+    #[test]
+    fn test_order() {
+        let queue = parse(SAMPLE_INPUT).unwrap();
+        let graph = PrecedenceGraph::new(&queue.rules);
+        assert_eq!(graph.is_ordered(&[75, 47, 61, 53, 29]), Ok(true));
+        assert_eq!(graph.first_violation(&[75, 97, 47, 61, 53]), Ok(Some((97, 75))));
+        assert_eq!(graph.order(&[75, 97, 47, 61, 53]), Ok(vec![97, 75, 47, 61, 53]));
+        assert_eq!(graph.order(&[97, 13, 75, 29, 47]), Ok(vec![97, 75, 47, 29, 13]));
+        assert_eq!(graph.order(&[]), Ok(vec![]));
+
+        // A repeated page is both before and after the others
+        assert_eq!(graph.is_ordered(&[47, 75, 47]), Err(OrderError::DuplicatePage(47)));
+        assert_eq!(graph.order(&[47, 75, 47]), Err(OrderError::DuplicatePage(47)));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let graph = PrecedenceGraph::new(&[(1, 2), (2, 3), (3, 4), (4, 2), (5, 1)]);
+        let error = graph.order(&[5, 4, 3, 2, 1]).unwrap_err();
+        assert_eq!(error, OrderError::Cycle(vec![2, 3, 4]));
+        assert_eq!(error.to_string(), "Pages 2 -> 3 -> 4 -> 2 form a cycle of rules");
+
+        // Without page 3, the rules about the update are fine
+        assert_eq!(graph.order(&[4, 2, 1, 5]), Ok(vec![4, 5, 1, 2]));
+    }
+
+    #[test]
+    fn test_analysis() {
+        let queue = parse(SAMPLE_INPUT).unwrap();
+        let graph = PrecedenceGraph::new(&queue.rules);
+        let chain = vec![(29, 13), (47, 61), (53, 29), (61, 53), (75, 47), (97, 75)];
+        assert_eq!(graph.minimal_rules(), Ok(chain.clone()));
+        assert_eq!(graph.redundant_rules().unwrap().len(), queue.rules.len() - chain.len());
+        assert_eq!(graph.total_order(), Some(vec![97, 75, 47, 61, 53, 29, 13]));
+
+        // A branch and a repeated rule
+        let graph = PrecedenceGraph::new(&[(1, 2), (1, 3), (2, 4), (3, 4), (1, 4), (1, 2)]);
+        assert_eq!(graph.redundant_rules(), Ok(vec![(1, 2), (1, 4)]));
+        assert_eq!(graph.minimal_rules(), Ok(vec![(1, 2), (1, 3), (2, 4), (3, 4)]));
+        assert_eq!(graph.total_order(), None);
+        assert_eq!(graph.pages(), vec![1, 2, 3, 4]);
+
+        // With cycles, (1, 2), (1, 3) and (3, 2) are each implied by the others, but cannot all be removed
+        let graph = PrecedenceGraph::new(&[(1, 2), (2, 3), (3, 1), (1, 3), (3, 2)]);
+        assert!(matches!(graph.redundant_rules(), Err(OrderError::Cycle(_))));
+        assert!(matches!(graph.minimal_rules(), Err(OrderError::Cycle(_))));
+        assert_eq!(graph.total_order(), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = PrecedenceGraph::new(&[(53, 13), (47, 53)]);
+        assert_eq!(graph.to_dot(), "digraph rules {\n    47 -> 53;\n    53 -> 13;\n}\n");
+    }
+}
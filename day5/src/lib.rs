@@ -1,142 +1,117 @@
-use regex::Regex;
+use std::collections::HashSet;
 
-static SEPARATOR: &str = "
+use parsing::{comma_list, parse_list, parse_number, split_sections, ParseError};
+use solution::{Answer, Solution};
 
-";
+#[cfg(feature = "generator")]
+pub mod generator;
+pub mod graph;
 
-pub fn part1(input: &str) -> i32
+use graph::{OrderError, PrecedenceGraph};
+
+/// Page ordering rules (`a|b` means `a` must be printed before `b`) and the
+/// list of updates (pages) to be printed
+pub struct PrintQueue {
+    pub rules: Vec<(i32, i32)>,
+    pub pages: Vec<Vec<i32>>
+}
+
+pub fn parse(input: &str) -> Result<PrintQueue, ParseError>
 {
     // Split text into rules and pages
-    let (rules_txt, pages_txt) = {
-        let sep_idx = input.find(SEPARATOR)
-            .expect("Failed to split input text. Line endings (CRLF vs LF) might be wrong");
-        let sep_len = SEPARATOR.len();
-        (&input[0..sep_idx], &input[sep_idx + sep_len..])
-    };
-    // println!("--{rules_txt}--");
-    // println!("--{pages_txt}--");
+    let (rules_txt, pages_txt) = split_sections(input, "rules and pages")?;
 
     // Parse rules into a Vec of tuples
-    let rules = Regex::new(r"(\d+)\|(\d+)").unwrap()
-        .captures_iter(rules_txt)
-        .map( |c| {
-            let n1 = c.get(1).unwrap().as_str().parse::<i32>().unwrap();
-            let n2 = c.get(2).unwrap().as_str().parse::<i32>().unwrap();
-            (n1, n2)
+    let rules = rules_txt.lines()
+        .map( |line| {
+            let (n1, n2) = line.split_once('|')
+                .ok_or_else(|| ParseError::unexpected(input, line, "a rule like `47|53`"))?;
+            Ok( (parse_number(input, n1, "a page number")?, parse_number(input, n2, "a page number")?) )
         })
-        .collect::<Vec<(i32,i32)>>();
-    // println!("--{rules:?}--");
-
-    // Parse pages into a Vec<Vec> of i32
-    let page_re = Regex::new(r"\d+").unwrap();
-    let pages : Vec<_> = pages_txt.lines()
-        .map( |line| page_re.captures_iter(line) )
-        .map( |c| c.map( |o| o.get(0).unwrap().as_str().parse::<i32>().unwrap() ).collect::<Vec<i32>>() )
-        .collect();
-
-    // Calculate "valid" pages
-    // let valid_pages = pages.iter()
-    //     .filter( |page| page_meets_rules(page, &rules) )
-    //     .collect::<Vec<_>>();
-    // println!("--{valid_pages:?}--");
+        .collect::<Result<Vec<(i32,i32)>, ParseError>>()?;
+
+    // Parse pages into a Vec<Vec> of i32, each page at most once
+    let pages = pages_txt.lines()
+        .map( |line| {
+            let page = parse_list(input, comma_list(line), "a page number")?;
+            let mut seen = HashSet::new();
+            match comma_list(line).zip(&page).find(|(_, n)| !seen.insert(**n)) {
+                Some((n_txt, _)) => Err(ParseError::unexpected(input, n_txt, "a page not already in the update")),
+                None             => Ok(page),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok( PrintQueue { rules, pages } )
+}
+
+pub fn part1(queue: &PrintQueue) -> i32
+{
+    let PrintQueue { rules, pages } = queue;
+    let graph = PrecedenceGraph::new(rules);
 
     // Sum the middle page of "valid" pages
     pages.iter()
-        .filter( |page| page_meets_rules(page, &rules) )
+        .filter( |page| graph.is_ordered(page) == Ok(true) )
         .map( |page| page[page.len()/2] )
         .sum()
 }
 
-// Check if a page meets the rules
-fn page_meets_rules(page: &Vec<i32>, rules: &Vec<(i32,i32)>) -> bool
+/// Fails on the first "invalid" update that cannot be reordered, because the rules about its pages form a cycle
+pub fn part2(queue: &PrintQueue) -> Result<i32, OrderError>
 {
-    for (n1, n2) in rules {
-        match (find_item(page, n1), find_item(page, n2)) {
-            (Some(pos1), Some(pos2)) => if pos2 < pos1 { return false; }
-            _ => continue
+    let PrintQueue { rules, pages } = queue;
+    let graph = PrecedenceGraph::new(rules);
+
+    // Sum the middle page of "invalid" pages after reordering
+    let mut sum = 0;
+    for page in pages {
+        if let Some((before, after)) = graph.first_violation(page)? {
+            trace::debug!("Broken rule: {before} should be left of {after} in {page:?}");
+            let ordered = graph.order(page)?;
+            sum += ordered[ordered.len()/2];
         }
     }
-    true
+    Ok(sum)
 }
 
+pub struct Day5;
 
+impl Solution for Day5 {
+    type Input = PrintQueue;
+    type Params = ();
 
-// Take a reference to an array-like (ie: like Vec) and to an Item
-// Return Some(<location in the array>) or None (if not present)
-use std::ops::Deref;
-fn find_item<I, T>(iterable: &I, item: &T) -> Option<usize>
-where   I: Deref<Target = [T]>,
-        T: std::cmp::PartialEq
-{
-    iterable.iter().position( |i| i == item )
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
 }
 
-pub fn part2(input: &str) -> i32
-{
-    // Split text into rules and pages
-    let (rules_txt, pages_txt) = {
-        let sep_idx = input.find(SEPARATOR)
-            .expect("Failed to split input text. Line endings (CRLF vs LF) might be wrong");
-        let sep_len = SEPARATOR.len();
-        (&input[0..sep_idx], &input[sep_idx + sep_len..])
-    };
-    // println!("--{rules_txt}--");
-    // println!("--{pages_txt}--");
 
-    // Parse rules into a Vec of tuples
-    let rules = Regex::new(r"(\d+)\|(\d+)").unwrap()
-        .captures_iter(rules_txt)
-        .map( |c| {
-            let n1 = c.get(1).unwrap().as_str().parse::<i32>().unwrap();
-            let n2 = c.get(2).unwrap().as_str().parse::<i32>().unwrap();
-            (n1, n2)
-        })
-        .collect::<Vec<(i32,i32)>>();
-    // println!("--{rules:?}--");
 
-    // Parse pages into a Vec<Vec> of i32
-    let page_re = Regex::new(r"\d+").unwrap();
-    let pages : Vec<_> = pages_txt.lines()
-        .map( |line| page_re.captures_iter(line) )
-        .map( |c| c.map( |o| o.get(0).unwrap().as_str().parse::<i32>().unwrap() ).collect::<Vec<i32>>() )
-        .collect();
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
 
-    // Sum the middle page of "invalid" pages after reordering
-    pages.iter()
-        .filter( |page| !page_meets_rules(page, &rules) )
-        .map( |page| fix_page_to_meet_rules(page, &rules) )
-        .map( |page| page[page.len()/2] )
-        .sum()
-}
+    solution::fixture_tests!(Day5: sample);
 
-// Check if a page meets the rules
-fn fix_page_to_meet_rules(page: &Vec<i32>, rules: &Vec<(i32,i32)>) -> Vec<i32>
-{
-    let mut new_page = page.clone();
-
-    while !page_meets_rules(&new_page, rules) {
-        // Iterate over every rule
-        for (n1, n2) in rules
-        {
-            // If they don't meet the rules, move left the right side
-            match (find_item(&new_page, n1), find_item(&new_page, n2))
-            {
-                (Some(pos1), Some(pos2)) =>
-                {
-                    // This item breaks the rule, mutate the vector and exit the for loop
-                    if pos2 < pos1
-                    {
-                        // println!("Vector status: {new_page:?}");
-                        // println!("Broken rule: {n1} should be left of {n2} but their respective positions were {pos1} and {pos2}");
-                        let misplaced_element = new_page.remove(pos1);
-                        new_page.insert(pos2, misplaced_element);
-                        break;
-                    }
-                }
-                _ => continue
-            }
-        }
+    #[test]
+    fn test_cycle() {
+        let queue = parse("1|2\n2|3\n3|1\n\n1,2\n3,2,1\n").unwrap();
+        assert_eq!(part1(&queue), 2);
+        assert_eq!(part2(&queue), Err(OrderError::Cycle(vec![1, 2, 3])));
+        assert_eq!(
+            Day5::part2(&queue, &()).to_string(),
+            "unsolved (Pages 1 -> 2 -> 3 -> 1 form a cycle of rules)"
+        );
     }
 
-    new_page
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_duplicate() {
+        assert_eq!(
+            parse("1|2\n\n1,2\n2,1,3,1\n").err(),
+            Some(ParseError::Unexpected(4, 7, "a page not already in the update".to_string(), "1".to_string()))
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+/// Shape of the generated lab
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Chance of a tile being an obstruction
+    pub obstructions: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 130, height: 130, obstructions: 0.01 }
+    }
+}
+
+/// Whether a guard walking from `start` facing `direction` leaves the lab, rather than walking in a loop
+fn escapes(grid: &[Vec<char>], start: (i32, i32), mut direction: (i32, i32)) -> bool {
+    let (height, width) = (grid.len() as i32, grid[0].len() as i32);
+    let mut position = start;
+    let mut seen = HashSet::new();
+
+    while seen.insert((position, direction)) {
+        let next = (position.0 + direction.0, position.1 + direction.1);
+        if !(0..height).contains(&next.0) || !(0..width).contains(&next.1) {
+            return true;
+        }
+        match grid[next.0 as usize][next.1 as usize] {
+            '#' => direction = (direction.1, -direction.0),
+            _   => position = next,
+        }
+    }
+    false
+}
+
+/// A lab map of `.` and `#` with a guard facing up (`^`). The guard always leaves the lab,
+/// the obstructions are drawn again until it does.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    loop {
+        let mut grid = (0..options.height)
+            .map(|_| (0..options.width)
+                .map(|_| if rng.random_bool(options.obstructions) { '#' } else { '.' })
+                .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let guard = (rng.random_range(0..options.height), rng.random_range(0..options.width));
+        grid[guard.0][guard.1] = '^';
+
+        if escapes(&grid, (guard.0 as i32, guard.1 as i32), (-1, 0)) {
+            return grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect();
+        }
+    }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_escapes() {
+        let grid = [".#..", "...#", "#^..", "..#."].iter().map(|row| row.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert!(!escapes(&grid, (2, 1), (-1, 0)));
+        assert!(escapes(&grid, (2, 1), (0, 1)));
+    }
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 20, height: 15, obstructions: 0.2 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(6), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(6), &options));
+        assert_eq!(input.matches('^').count(), 1);
+        assert!(part1(&parse(&input).unwrap()).unwrap() >= 1);
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use rectangle::{Direction, Rectangle, Rectangular, RectangularData};
+
+/// The guard never leaves the lab
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy)]
+#[error("The guard walks in a loop and never leaves the lab")]
+pub struct Loop;
+
+/// Where a guard walking from a cell stops in front of an obstacle, for each direction
+/// (in the order of `Rectangular::adjacent_coordinates`). `None` when it walks out of the lab.
+type Jumps = [Option<(usize, usize)>; 4];
+
+fn slot(direction: Direction) -> usize {
+    match direction {
+        Direction::Up    => 0,
+        Direction::Down  => 1,
+        Direction::Left  => 2,
+        Direction::Right => 3,
+    }
+}
+
+
+// ------------ GuardSim class ------------
+/// The lab and the guard walking in it. Walks go from obstacle to obstacle with jump tables instead of cell by cell,
+/// and an extra obstacle is checked against each jump, so that trying one does not change (or copy) the lab.
+#[derive(Debug, Clone)]
+pub struct GuardSim {
+    obstacles: Rectangle<bool>,
+    start: (usize, usize),
+    facing: Direction,
+    jumps: Rectangle<Jumps>
+}
+
+impl GuardSim {
+    pub fn new(obstacles: Rectangle<bool>, start: (usize, usize), facing: Direction) -> Self {
+        let mut jumps = Rectangle::from_repeated_element([None; 4], obstacles.get_height(), obstacles.get_width());
+
+        // Each jump is the one of the next cell, so the next cell is filled first
+        let mut coords = obstacles.iter_coord().collect::<Vec<_>>();
+        for direction in [Direction::Up, Direction::Left, Direction::Down, Direction::Right] {
+            if direction == Direction::Down {
+                coords.reverse();
+            }
+
+            for &coord in &coords {
+                let jump = match obstacles.next_coord(&coord, direction) {
+                    None                                         => None,
+                    Some(next) if *obstacles.get(&next).unwrap() => Some(coord),
+                    Some(next)                                   => jumps.data[next.0][next.1][slot(direction)],
+                };
+                jumps.data[coord.0][coord.1][slot(direction)] = jump;
+            }
+        }
+
+        GuardSim { obstacles, start, facing, jumps }
+    }
+
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// Every cell the guard walks on before leaving the lab, in the order it first gets there.
+    /// Loops are found like in `loops_with`.
+    pub fn visited(&self) -> Result<Vec<(usize, usize)>, Loop> {
+        let mut seen = Rectangle::from_repeated_element(false, self.obstacles.get_height(), self.obstacles.get_width());
+        let mut visited = Vec::new();
+        let mut turns = HashSet::new();
+        let (mut coord, mut direction) = (self.start, self.facing);
+
+        loop {
+            let stop = self.jump(coord, direction, None);
+            let mut cell = Some(coord);
+            while let Some(current) = cell {
+                if !seen.data[current.0][current.1] {
+                    seen.data[current.0][current.1] = true;
+                    visited.push(current);
+                }
+                cell = (Some(current) != stop).then(|| self.obstacles.next_coord(&current, direction)).flatten();
+            }
+
+            match stop {
+                Some(stop) if !turns.insert((stop, direction)) => return Err(Loop),
+                Some(stop) => (coord, direction) = (stop, direction.turn_right()),
+                None       => return Ok(visited),
+            }
+        }
+    }
+
+    /// Whether the guard walks in a loop once an obstacle is added at `extra`. Only the states where
+    /// the guard turns are remembered: a loop comes back to one of them.
+    pub fn loops_with(&self, extra: (usize, usize)) -> bool {
+        let mut turns = HashSet::new();
+        let (mut coord, mut direction) = (self.start, self.facing);
+
+        while let Some(stop) = self.jump(coord, direction, Some(extra)) {
+            if !turns.insert((stop, direction)) {
+                return true;
+            }
+            (coord, direction) = (stop, direction.turn_right());
+        }
+        false
+    }
+
+    /// Where the guard stops walking from `coord` towards `direction`, if it does not leave the lab
+    fn jump(&self, coord: (usize, usize), direction: Direction, extra: Option<(usize, usize)>) -> Option<(usize, usize)> {
+        let stop = self.jumps.data[coord.0][coord.1][slot(direction)];
+        let Some(to_extra) = extra.and_then(|extra| distance_ahead(coord, extra, direction)) else {
+            return stop;
+        };
+
+        // The extra obstacle matters if the guard would walk over it
+        match stop.map(|stop| distance_ahead(coord, stop, direction).unwrap_or(0)) {
+            Some(to_stop) if to_stop < to_extra => stop,
+            _ => {
+                let before_extra = to_extra - 1;
+                Some( match direction {
+                    Direction::Up    => (coord.0 - before_extra, coord.1),
+                    Direction::Down  => (coord.0 + before_extra, coord.1),
+                    Direction::Left  => (coord.0, coord.1 - before_extra),
+                    Direction::Right => (coord.0, coord.1 + before_extra),
+                })
+            }
+        }
+    }
+}
+
+/// Number of steps from `from` to `to`, if `to` is ahead on the same row or column
+fn distance_ahead(from: (usize, usize), to: (usize, usize), direction: Direction) -> Option<usize> {
+    let distance = match direction {
+        Direction::Up    if to.1 == from.1 => from.0.checked_sub(to.0),
+        Direction::Down  if to.1 == from.1 => to.0.checked_sub(from.0),
+        Direction::Left  if to.0 == from.0 => from.1.checked_sub(to.1),
+        Direction::Right if to.0 == from.0 => to.1.checked_sub(from.1),
+        _ => None,
+    };
+    distance.filter(|&distance| distance > 0)
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parse;
+
+    static SAMPLE_INPUT: &str = include_str!("../data/sample.txt");
+
+    #[test]
+    fn test_jumps() {
+        let sim = parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(sim.start(), (6, 4));
+        assert_eq!(sim.jump((6, 4), Direction::Up, None), Some((1, 4)));
+        assert_eq!(sim.jump((1, 4), Direction::Right, None), Some((1, 8)));
+        assert_eq!(sim.jump((7, 7), Direction::Down, None), None);
+
+        // An extra obstacle in the way, behind the guard, or beyond the next obstacle
+        assert_eq!(sim.jump((6, 4), Direction::Up, Some((3, 4))), Some((4, 4)));
+        assert_eq!(sim.jump((6, 4), Direction::Up, Some((5, 4))), Some((6, 4)));
+        assert_eq!(sim.jump((6, 4), Direction::Up, Some((8, 4))), Some((1, 4)));
+        assert_eq!(sim.jump((1, 4), Direction::Right, Some((1, 9))), Some((1, 8)));
+        assert_eq!(sim.jump((7, 7), Direction::Down, Some((9, 7))), Some((8, 7)));
+    }
+
+    #[test]
+    fn test_visited() {
+        let sim = parse(SAMPLE_INPUT).unwrap();
+        assert_eq!(sim.visited().map(|visited| visited.len()), Ok(41));
+
+        let looping = parse(".#...\n....#\n.^...\n#....\n...#.\n").unwrap();
+        assert_eq!(looping.visited(), Err(Loop));
+        assert!(looping.loops_with((0, 0)));
+    }
+
+    #[test]
+    fn test_loops_with() {
+        let sim = parse(SAMPLE_INPUT).unwrap();
+        assert!(sim.loops_with((6, 3)));
+        assert!(sim.loops_with((9, 7)));
+        assert!(!sim.loops_with((0, 0)));
+
+        // Blocked right away: the guard turns in place
+        assert!(!sim.loops_with((5, 4)));
+    }
+}
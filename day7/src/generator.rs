@@ -0,0 +1,82 @@
+use rand::Rng;
+
+/// Shape of the generated calibration equations
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub equations: usize,
+    /// Smallest and largest number of operands in an equation
+    pub operands: (usize, usize),
+    /// Largest operand, the smallest one is 1
+    pub max_operand: i64,
+    /// Chance of the test value being made with `+`, `*` and `||` from the operands, instead of being off by a bit
+    pub solvable: f64
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { equations: 850, operands: (2, 12), max_operand: 999, solvable: 0.6 }
+    }
+}
+
+/// Digits of a number
+fn digits(n: i64) -> u32 {
+    n.ilog10() + 1
+}
+
+/// Equations like `190: 10 19`. Operands have at most 18 digits in total, so that any combination
+/// of operators stays below their concatenation and fits in an `i64`.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut output = String::new();
+
+    for _ in 0..options.equations {
+        let count = rng.random_range(options.operands.0..=options.operands.1).min(18);
+
+        // Spend the digits budget leaving at least one digit for each remaining operand
+        let mut budget = 18;
+        let operands = (0..count as u32)
+            .map(|idx| {
+                let most = digits(options.max_operand).min(budget - (count as u32 - idx - 1));
+                let length = rng.random_range(1..=most);
+                budget -= length;
+                rng.random_range(10_i64.pow(length - 1)..=(10_i64.pow(length) - 1).min(options.max_operand))
+            })
+            .collect::<Vec<_>>();
+
+        let total = operands[1..].iter().fold(operands[0], |acc, &n| match rng.random_range(0..3) {
+            0 => acc + n,
+            1 => acc * n,
+            _ => acc * 10_i64.pow(digits(n)) + n,
+        });
+        let total = match rng.random_bool(options.solvable) {
+            true  => total,
+            false => total + rng.random_range(1..=10),
+        };
+
+        let operands = operands.iter().map(i64::to_string).collect::<Vec<_>>().join(" ");
+        output += &format!("{total}: {operands}\n");
+    }
+    output
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { equations: 50, solvable: 1.0, ..Default::default() };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(7), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(7), &options));
+
+        // Every equation can be made true
+        let equations = parse(&input).unwrap();
+        assert_eq!(part2(&equations), equations.iter().map(|(total, _)| total).sum());
+    }
+}
@@ -1,29 +1,34 @@
-use regex::Regex;
+use parsing::{parse_list, parse_number, space_list, ParseError};
+use solution::{Answer, Solution};
 
-pub fn part1(input: &str) -> i64
+#[cfg(feature = "generator")]
+pub mod generator;
+
+/// Calibration equations: (test value, numbers)
+pub fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError>
 {
     // Parse input
-    let re = Regex::new(r"(\d+): ([\d ]+)").unwrap();
     let rows = input.lines()
         .map( |line|
         {
-            re.captures(line)
-                .map( |c| (c.get(1).unwrap().as_str(), c.get(2).unwrap().as_str()) )
-                .map( |(total_str, numbers_str)|
-                (
-                    total_str.parse::<i64>().unwrap_or_else(|_| {println!("Failed to unwrap `{total_str}`"); panic!()}),
-                    numbers_str.split(' ')
-                        .map( |num_str| num_str.parse::<i64>().unwrap() )
-                        .collect::<Vec<i64>>()
-                ))
-                .unwrap()
+            let (total_str, numbers_str) = line.split_once(": ")
+                .ok_or_else(|| ParseError::unexpected(input, line, "an equation like `190: 10 19`"))?;
+            Ok((
+                parse_number(input, total_str, "a test value")?,
+                parse_list(input, space_list(numbers_str), "a number")?
+            ))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
     // Verify parsed input
     //rows.into_iter().for_each( |(n, v)| println!("Total is {n}, numbers are {v:?}"));
 
-    rows.into_iter()
+    Ok(rows)
+}
+
+pub fn part1(rows: &[(i64, Vec<i64>)]) -> i64
+{
+    rows.iter()
         .filter( |(total, numbers)|
         {
             // Put the first number in a vector
@@ -48,30 +53,9 @@ pub fn part1(input: &str) -> i64
         .sum()
 }
 
-pub fn part2(input: &str) -> i64
+pub fn part2(rows: &[(i64, Vec<i64>)]) -> i64
 {
-    // Parse input
-    let re = Regex::new(r"(\d+): ([\d ]+)").unwrap();
-    let rows = input.lines()
-        .map( |line|
-        {
-            re.captures(line)
-                .map( |c| (c.get(1).unwrap().as_str(), c.get(2).unwrap().as_str()) )
-                .map( |(total_str, numbers_str)|
-                (
-                    total_str.parse::<i64>().unwrap_or_else(|_| {println!("Failed to unwrap `{total_str}`"); panic!()}),
-                    numbers_str.split(' ')
-                        .map( |num_str| num_str.parse::<i64>().unwrap() )
-                        .collect::<Vec<i64>>()
-                ))
-                .unwrap()
-        })
-        .collect::<Vec<_>>();
-
-    // Verify parsed input
-    //rows.into_iter().for_each( |(n, v)| println!("Total is {n}, numbers are {v:?}"));
-
-    rows.into_iter()
+    rows.iter()
         .filter( |(total, numbers)|
         {
             // Put the first number in a vector
@@ -104,10 +88,23 @@ fn combine_numbers(v1: i64, v2: i64) -> i64 {
     v1 * (10_i64).pow(1 + v2.ilog10()) + v2
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(i64, Vec<i64>)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    solution::fixture_tests!(Day7: sample);
+
     #[test]
     fn a_few_examples() {
         let result = combine_numbers(2, 2);
@@ -122,4 +119,17 @@ mod tests {
         let result = combine_numbers(99999, 88888);
         assert_eq!(result, 9999988888);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("190: 10 19\n3267: 81 40 27"), Ok(vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])]));
+        assert_eq!(
+            parse("190: 10 19\n3267 81 40 27"),
+            Err(ParseError::Unexpected(2, 1, "an equation like `190: 10 19`".to_string(), "3267 81 40 27".to_string()))
+        );
+        assert_eq!(
+            parse("190: 10 1x9"),
+            Err(ParseError::Unexpected(1, 9, "a number".to_string(), "1x9".to_string()))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use rand::Rng;
+
+/// Characters that can name a frequency
+static FREQUENCIES: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Shape of the generated antenna map
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Number of different frequencies, up to 62
+    pub frequencies: usize,
+    /// Antennas of each frequency
+    pub antennas: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { width: 50, height: 50, frequencies: 40, antennas: 4 }
+    }
+}
+
+/// A map of `.` with antennas named by a digit or a letter. Antennas never share a tile,
+/// so the map must have room for all of them.
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    assert!(options.frequencies * options.antennas <= options.width * options.height, "Too many antennas for the map");
+
+    let mut grid = vec![vec!['.'; options.width]; options.height];
+    for frequency in FREQUENCIES.chars().take(options.frequencies) {
+        for _ in 0..options.antennas {
+            loop {
+                let (row, col) = (rng.random_range(0..options.height), rng.random_range(0..options.width));
+                if grid[row][col] == '.' {
+                    grid[row][col] = frequency;
+                    break;
+                }
+            }
+        }
+    }
+
+    grid.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{parse, part1, part2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { width: 12, height: 10, frequencies: 62, antennas: 1 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(8), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(8), &options));
+        assert_eq!(input.chars().filter(char::is_ascii_alphanumeric).count(), 62);
+
+        // A single antenna per frequency has no antinodes
+        let map = parse(&input).unwrap();
+        assert_eq!((part1(&map), part2(&map)), (0, 0));
+    }
+}
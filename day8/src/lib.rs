@@ -3,17 +3,27 @@ use std::collections::HashSet;
 
 use itertools::iproduct;
 
+use parsing::{char_grid, ParseError};
+use solution::{Answer, Solution};
 
-fn parse_input(input: &str) -> (HashMap<char, Vec<(i32, i32)>>, (i32, i32))
+#[cfg(feature = "generator")]
+pub mod generator;
+
+/// Location of every antenna grouped by frequency, and the map size
+pub struct AntennaMap {
+    locations: HashMap<char, Vec<(i32, i32)>>,
+    width: i32,
+    height: i32
+}
+
+pub fn parse(input: &str) -> Result<AntennaMap, ParseError>
 {
     let mut antennas_locations: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
 
-    let char_table = input.lines()
-        .map( |line|  line.chars().collect::<Vec<char>>() )
-        .collect::<Vec<Vec<char>>>();
+    let char_table = char_grid(input, |ch| ch == '.' || ch.is_ascii_alphanumeric(), "`.` or an antenna frequency")?;
 
     let height = char_table.len() as i32;
-    let width = char_table[0].len() as i32;
+    let width = char_table.first().map(Vec::len).unwrap_or(0) as i32;
 
     char_table.iter()
         .enumerate()
@@ -31,12 +41,12 @@ fn parse_input(input: &str) -> (HashMap<char, Vec<(i32, i32)>>, (i32, i32))
                 .or_insert_with(|| vec!{(col_idx, line_idx)} );
         });
 
-    (antennas_locations, (width, height))
+    Ok( AntennaMap { locations: antennas_locations, width, height } )
 }
 
-pub fn part1(input: &str) -> usize
+pub fn part1(map: &AntennaMap) -> usize
 {
-    let (antennas_locations, (width, height)) = parse_input(input);
+    let &AntennaMap { locations: ref antennas_locations, width, height } = map;
 
     let antinodes = antennas_locations.keys()
         .flat_map( |ch| iproduct!(antennas_locations[ch].iter(), antennas_locations[ch].iter()) )
@@ -48,9 +58,27 @@ pub fn part1(input: &str) -> usize
     antinodes.len()
 }
 
-pub fn part2(input: &str) -> usize
+/// The map with its antennas, and `#` on the antinodes that are not antennas
+fn draw(map: &AntennaMap, antinodes: &HashSet<(i32, i32)>) -> String
+{
+    let antennas = map.locations.iter()
+        .flat_map( |(&ch, locations)| locations.iter().map(move |&location| (location, ch)) )
+        .collect::<HashMap<(i32, i32), char>>();
+
+    (0..map.height)
+        .map( |line_idx| (0..map.width)
+            .map( |col_idx| match antennas.get(&(col_idx, line_idx)) {
+                Some(&ch)                                        => ch,
+                None if antinodes.contains(&(col_idx, line_idx)) => '#',
+                None                                             => '.',
+            })
+            .collect::<String>() + "\n")
+        .collect()
+}
+
+pub fn part2(map: &AntennaMap) -> usize
 {
-    let (antennas_locations, (width, height)) = parse_input(input);
+    let &AntennaMap { locations: ref antennas_locations, width, height } = map;
 
     let antinodes = antennas_locations.keys()
         .flat_map( |ch| iproduct!(antennas_locations[ch].iter(), antennas_locations[ch].iter()) )
@@ -72,36 +100,29 @@ pub fn part2(input: &str) -> usize
         })
         .collect::<HashSet<(i32,i32)>>();
 
-
-    // DEBUG PRINT
-    //let antennas = antennas_locations.values()
-    //    .flat_map( |v| v.clone().into_iter() )
-    //    .collect::<HashSet<(i32,i32)>>();
-    //
-    //input.lines()
-    //    .enumerate()
-    //    .for_each( |(line_idx, line)|
-    //    {
-    //        line.chars()
-    //            .enumerate()
-    //            .for_each( |(col_idx, ch)|
-    //            {
-    //                let coord = (col_idx as i32, line_idx as i32);
-    //                if antennas.contains(&coord)
-    //                {
-    //                    print!("{ch}");
-    //                }
-    //                else if antinodes.contains(&coord)
-    //                {
-    //                    print!("#");
-    //                }
-    //                else
-    //                {
-    //                    print!(".");
-    //                }
-    //            });
-    //        print!("\n");
-    //    });
+    trace::trace!("\n{}", draw(map, &antinodes));
 
     antinodes.len()
 }
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = AntennaMap;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day8: sample);
+}
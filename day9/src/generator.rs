@@ -0,0 +1,53 @@
+use rand::Rng;
+
+/// Shape of the generated disk map
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub files: usize,
+    /// Largest gap between two files, up to 9
+    pub max_gap: u32
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { files: 10000, max_gap: 9 }
+    }
+}
+
+/// A dense disk map: a digit for the size of each file (1 to 9), alternating with the size of the free space after it
+pub fn generate<R: Rng>(rng: &mut R, options: &Options) -> String {
+    let mut output = String::new();
+
+    for idx in 0..options.files {
+        output.push(char::from_digit(rng.random_range(1..=9), 10).unwrap());
+        if idx + 1 < options.files {
+            output.push(char::from_digit(rng.random_range(0..=options.max_gap.min(9)), 10).unwrap());
+        }
+    }
+    output + "\n"
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::{disk_to_string, parse};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_generate() {
+        let options = Options { files: 25, max_gap: 3 };
+        let input = generate(&mut ChaCha8Rng::seed_from_u64(9), &options);
+        assert_eq!(input, generate(&mut ChaCha8Rng::seed_from_u64(9), &options));
+        assert_eq!(input.trim_end().len(), 2 * 25 - 1);
+
+        // More than ten files can be shown
+        let disk = parse(&input).unwrap();
+        let blocks = input.trim_end().chars().map(|c| c.to_digit(10).unwrap() as usize).sum::<usize>();
+        assert_eq!(disk_to_string(&disk).len(), blocks);
+    }
+}
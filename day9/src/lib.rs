@@ -1,5 +1,11 @@
 use std::{cmp};
 
+use parsing::ParseError;
+use solution::{Answer, Solution};
+
+#[cfg(feature = "generator")]
+pub mod generator;
+
 #[derive(Copy, Clone, Debug)]
 pub enum MemoryBlock {
     Empty {size: u32},
@@ -68,41 +74,54 @@ pub fn move_fragments(dest: &mut MemoryBlock, source: &mut MemoryBlock) -> Memor
     }
 }
 
-pub fn print_disk(disk: &[MemoryBlock])
+/// The disk as in the puzzle text, with each file shown by the last digit of its id
+pub fn disk_to_string(disk: &[MemoryBlock]) -> String
 {
     disk.iter()
-        .for_each(|m|
+        .map(|m|
         {
             let (size, character) = match m
             {
                 MemoryBlock::Empty{size}             => (size, '.'),
-                MemoryBlock::File { size, file_idx } => (size,  char::from_digit(*file_idx, 10).unwrap())
+                MemoryBlock::File { size, file_idx } => (size,  char::from_digit(*file_idx % 10, 10).unwrap())
             };
-            for _ in 0..*size {
-                print!("{character}");
-            }
-        });
-    println!();
+            character.to_string().repeat(*size as usize)
+        })
+        .collect()
 }
 
-fn parse_input(input: &str) -> Vec<MemoryBlock>
+/// Print the disk on stderr, for debugging
+pub fn print_disk(disk: &[MemoryBlock])
+{
+    eprintln!("{}", disk_to_string(disk));
+}
+
+pub fn parse(input: &str) -> Result<Vec<MemoryBlock>, ParseError>
 {
     let mut empty_space = true;
     let mut file_idx = 0;
 
-    input.chars()
+    let disk_map = input.trim_end()
+        .chars()
         .enumerate()
         .map(|(idx, size)|
         {
             empty_space = !empty_space;
             file_idx += if empty_space { 1 } else { 0 };
-            MemoryBlock::new
+            Ok( MemoryBlock::new
             (
-                size.to_digit(10).unwrap_or_else( || panic!("Invalid digit `{size}` at {idx}")),
+                size.to_digit(10)
+                    .ok_or_else(|| ParseError::Unexpected(1, idx + 1, "a digit".to_string(), size.to_string()))?,
                 if !empty_space { Some(file_idx) } else { None }
-            )
+            ))
         })
-        .collect()
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    if disk_map.is_empty() {
+        return Err(ParseError::missing("disk map"));
+    }
+
+    Ok(disk_map)
 }
 
 fn calculate_checksum(disk: &[MemoryBlock]) -> u64
@@ -122,23 +141,22 @@ fn calculate_checksum(disk: &[MemoryBlock]) -> u64
                 {
                     let start = counter;
                     let end = counter + size - 1;
-                    let sum_of_counter_values: u32 = (start..(end + 1)).sum();
+                    let sum_of_counter_values: u64 = (start as u64..(end as u64 + 1)).sum();
                     //let sum_of_counter_values = size * (2*counter + (size-1)) / 2; // Better but less readable
 
-                    //println!("Checksum: starting at {counter}, with size {size} to {end}, with sum {sum_of_counter_values} or {option_2}");
+                    trace::trace!("Checksum: file {file_idx} from block {start} to {end}, with sum {sum_of_counter_values}");
 
                     counter += size;
-                    (sum_of_counter_values * file_idx) as u64
+                    sum_of_counter_values * *file_idx as u64
                 }
             }
         })
         .sum()
 }
 
-pub fn part1(input: &str) -> u64
+pub fn part1(disk_map: &[MemoryBlock]) -> u64
 {
-    let disk_map = parse_input(input);
-    //print_disk(&disk_map);
+    trace::trace!("{}", disk_to_string(disk_map));
 
     // Use 2 indices to move from the edges of the vector until they converge
     let mut left_idx  = 0;
@@ -173,8 +191,9 @@ pub fn part1(input: &str) -> u64
         {
             // Subtract the size from left and right, and create a new memory block to be inserted
             let moved_block = move_fragments(/* dest */ &mut left, /* source */ &mut right);
+            trace::debug!("Moved {moved_block:?}");
             defrag_disk.push(moved_block);
-            //print_disk(&defrag_disk);
+            trace::trace!("{}", disk_to_string(&defrag_disk));
         }
         else
         {
@@ -187,7 +206,7 @@ pub fn part1(input: &str) -> u64
         defrag_disk.push(right);
     }
 
-    //print_disk(&defrag_disk);
+    trace::trace!("{}", disk_to_string(&defrag_disk));
 
     // Everything left in the disk should be files
     assert!( defrag_disk.iter().all(|m| m.is_file() ) );
@@ -196,10 +215,10 @@ pub fn part1(input: &str) -> u64
     calculate_checksum(&defrag_disk)
 }
 
-pub fn part2(input: &str) -> u64
+pub fn part2(disk_map: &[MemoryBlock]) -> u64
 {
-    let mut disk_map = parse_input(input);
-    //print_disk(&disk_map);
+    let mut disk_map = disk_map.to_vec();
+    trace::trace!("{}", disk_to_string(&disk_map));
 
     // Get a copy of all the files to be iterated in reverse order
     let files = disk_map.iter()
@@ -229,14 +248,16 @@ pub fn part2(input: &str) -> u64
         // If there are not spaces available, skip this file, otherwise extract position in the array and size
         let Some((empty_pos, empty_size)) = leftmost_sufficiently_big_empty_space else
         {
+            trace::debug!("No space for {file:?}");
             continue;
         };
+        trace::debug!("Moved {file:?} from block {file_pos} to block {empty_pos}");
 
         // If they space and the file are the same size, do a clean swap
         if empty_size == file_size
         {
             disk_map.swap(empty_pos, file_pos);
-            // print_disk(&disk_map);
+            trace::trace!("{}", disk_to_string(&disk_map));
         }
         else
         {
@@ -249,10 +270,38 @@ pub fn part2(input: &str) -> u64
             // represented by inserting a new empty space block with the difference in size
             let remaining_space_after_swap = MemoryBlock::Empty { size: empty_size - file_size};
             disk_map.insert(empty_pos + 1, remaining_space_after_swap);
-            // print_disk(&disk_map);
+            trace::trace!("{}", disk_to_string(&disk_map));
         }
     }
 
     // Calculate checksum
     calculate_checksum(&disk_map)
 }
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<MemoryBlock>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> { parse(input) }
+    fn part1(input: &Self::Input, _: &()) -> Answer { part1(input).into() }
+    fn part2(input: &Self::Input, _: &()) -> Answer { part2(input).into() }
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    solution::fixture_tests!(Day9: sample);
+
+    #[test]
+    fn test_checksum_beyond_32_bits() {
+        let disk = [MemoryBlock::Empty { size: 1_000_000 }, MemoryBlock::File { size: 1, file_idx: 10_000 }];
+        assert_eq!(calculate_checksum(&disk), 10_000_000_000);
+    }
+}
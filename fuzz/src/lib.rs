@@ -0,0 +1,50 @@
+//! Fuzz target bodies, shared by the libFuzzer binaries and the regression corpus test.
+//! Any input must give either an `Ok` or a typed error, never a panic.
+
+use std::str;
+
+use day17::{Computer, Day17, MAX_STEPS};
+use rectangle::Rectangle;
+use solution::Solution;
+
+pub fn rectangle(data: &[u8]) {
+    if let Ok(text) = str::from_utf8(data) {
+        let _ = Rectangle::<u32>::from_num_str(text);
+        let _ = Rectangle::from_char_str(text);
+    }
+}
+
+pub fn day9_parse(data: &[u8]) {
+    if let Ok(text) = str::from_utf8(data) {
+        let _ = day9::parse(text);
+    }
+}
+
+pub fn day17_parse(data: &[u8]) {
+    if let Ok(text) = str::from_utf8(data) {
+        if let Ok(cpu) = day17::parse(text) {
+            let _ = Day17::part1(&cpu, &());
+            let _ = Day17::part2(&cpu, &());
+        }
+    }
+}
+
+/// The first 24 bytes are the A, B and C registers, the rest is the program (one 3-bit value per byte)
+pub fn day17_computer(data: &[u8]) {
+    let (registers, program) = data.split_at(data.len().min(24));
+    let mut registers = registers.chunks(8)
+        .map(|bytes| bytes.iter().fold(0, |register, &byte| (register << 8) | byte as u64));
+    let (a, b, c) = (registers.next().unwrap_or(0), registers.next().unwrap_or(0), registers.next().unwrap_or(0));
+    let program = program.iter().map(|&byte| (byte % 8) as u64).collect();
+
+    let cpu = Computer::new(a, b, c, program);
+    let _ = cpu.clone().run_for(MAX_STEPS);
+    let _ = Day17::part1(&cpu, &());
+    let _ = Day17::part2(&cpu, &());
+}
+
+pub fn day19_parse(data: &[u8]) {
+    if let Ok(text) = str::from_utf8(data) {
+        let _ = day19::parse(text);
+    }
+}
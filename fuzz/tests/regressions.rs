@@ -0,0 +1,29 @@
+use std::fs;
+use std::path::Path;
+
+/// Replay every input of a target's regression corpus
+fn replay(target: &str, fuzz: fn(&[u8])) {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("regressions").join(target);
+    let entries = fs::read_dir(&directory)
+        .unwrap_or_else(|error| panic!("Cannot read {}: {error}", directory.display()));
+
+    for entry in entries {
+        let path = entry.unwrap().path();
+        fuzz(&fs::read(&path).unwrap());
+    }
+}
+
+#[test]
+fn test_rectangle() { replay("rectangle", fuzz::rectangle); }
+
+#[test]
+fn test_day9_parse() { replay("day9_parse", fuzz::day9_parse); }
+
+#[test]
+fn test_day17_parse() { replay("day17_parse", fuzz::day17_parse); }
+
+#[test]
+fn test_day17_computer() { replay("day17_computer", fuzz::day17_computer); }
+
+#[test]
+fn test_day19_parse() { replay("day19_parse", fuzz::day19_parse); }
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::error::Error;
+use std::str::FromStr;
+
+
+// ------------ ParseError class ------------
+/// Reasons why a puzzle input could not be parsed.
+///
+/// Lines and columns are 1-based, as shown by text editors.
+#[derive(PartialEq, Debug)]
+pub enum ParseError {
+    /// Found something else at (line, column): (line, column, expected, found)
+    Unexpected(usize, usize, String, String),
+    /// A required part of the input is not present at all
+    Missing(String),
+    /// The input is malformed, but not at a single location
+    Invalid(String)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unexpected(line, col, expected, found) if found.is_empty() =>
+                write!(f, "Expected {expected} but found nothing at line {line}, column {col}."),
+            ParseError::Unexpected(line, col, expected, found) =>
+                write!(f, "Expected {expected} but found `{found}` at line {line}, column {col}."),
+            ParseError::Missing(what) => write!(f, "Missing {what}."),
+            ParseError::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+impl Error for ParseError {}
+
+impl ParseError {
+    /// Error pointing at `found`, which must be a slice of `input`
+    pub fn unexpected(input: &str, found: &str, expected: &str) -> Self {
+        let (line, col) = location(input, found);
+        ParseError::Unexpected(line, col, expected.to_string(), found.to_string())
+    }
+
+    /// Error for a required part of the input that is not present
+    pub fn missing(what: &str) -> Self {
+        ParseError::Missing(what.to_string())
+    }
+
+    /// Same error, for a line that was parsed on its own (ie: read from a stream) but is line `line` of the input
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            ParseError::Unexpected(_, col, expected, found) => ParseError::Unexpected(line, col, expected, found),
+            error                                           => error,
+        }
+    }
+}
+
+/// Line and column (1-based) where `part` starts. `part` must be a slice of `input`.
+pub fn location(input: &str, part: &str) -> (usize, usize) {
+    let offset = (part.as_ptr() as usize).saturating_sub(input.as_ptr() as usize).min(input.len());
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+
+// ------------ Sections ------------
+/// Iterator over the blocks of a text separated by empty lines
+pub struct Sections<'a> {
+    rest: &'a str
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in self.rest.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                // An empty line ends the section, unless the section has not started yet
+                if start.is_some() {
+                    break;
+                }
+            }
+            else {
+                start.get_or_insert(offset);
+                end = offset + line.trim_end_matches(['\n', '\r']).len();
+            }
+            offset += line.len();
+        }
+
+        let section = start.map(|start| &self.rest[start..end]);
+        self.rest = &self.rest[offset..];
+        section
+    }
+}
+
+/// Split the input in blocks separated by empty lines (LF or CRLF line endings).
+/// The blocks do not include the trailing line ending.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections { rest: input }
+}
+
+/// Split the input in exactly two sections (see `sections`)
+pub fn split_sections<'a>(input: &'a str, what: &str) -> Result<(&'a str, &'a str), ParseError> {
+    let mut iter = sections(input);
+
+    let (Some(first), Some(second)) = (iter.next(), iter.next()) else {
+        return Err(ParseError::missing(&format!("empty line separating {what}")));
+    };
+
+    match iter.next() {
+        Some(extra) => Err(ParseError::unexpected(input, extra, "the end of the input")),
+        None        => Ok( (first, second) )
+    }
+}
+
+
+// ------------ Numbers ------------
+/// Parse `token` (a slice of `input`) as a number
+pub fn parse_number<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse::<T>()
+        .map_err(|_| ParseError::unexpected(input, token, expected))
+}
+
+/// Iterator over the numbers found in a text, ignoring everything else
+pub struct Integers<'a, T> {
+    input: &'a str,
+    rest: &'a str,
+    signed: bool,
+    marker: std::marker::PhantomData<T>
+}
+
+impl<'a, T: FromStr> Iterator for Integers<'a, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.rest.as_bytes();
+
+        // Find the first digit, and include a preceding minus sign for signed numbers
+        let first_digit = bytes.iter().position(u8::is_ascii_digit)?;
+        let start = if self.signed && first_digit > 0 && bytes[first_digit - 1] == b'-' { first_digit - 1 } else { first_digit };
+        let end = bytes[first_digit..].iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |len| first_digit + len);
+
+        let token = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some( parse_number(self.input, token, "a number that fits its type") )
+    }
+}
+
+/// Extract every signed integer (ie: `-3`) in `text`, a slice of `input`
+pub fn signed_integers<'a, T: FromStr>(input: &'a str, text: &'a str) -> Integers<'a, T> {
+    Integers { input, rest: text, signed: true, marker: std::marker::PhantomData }
+}
+
+/// Extract every unsigned integer in `text`, a slice of `input`. Minus signs are ignored.
+pub fn unsigned_integers<'a, T: FromStr>(input: &'a str, text: &'a str) -> Integers<'a, T> {
+    Integers { input, rest: text, signed: false, marker: std::marker::PhantomData }
+}
+
+/// Take exactly N numbers from `numbers`. Otherwise, `text` (a slice of `input`) is reported.
+pub fn exactly<T, const N: usize>(numbers: Integers<'_, T>, text: &str, expected: &str) -> Result<[T; N], ParseError>
+where T: FromStr
+{
+    let input = numbers.input;
+    numbers.collect::<Result<Vec<T>, _>>()?
+        .try_into()
+        .map_err(|_| ParseError::unexpected(input, text, expected))
+}
+
+
+// ------------ Records and lists ------------
+/// Split a `key: value` line, which must be a slice of `input`
+pub fn record<'a>(input: &str, line: &'a str) -> Result<(&'a str, &'a str), ParseError> {
+    line.split_once(": ")
+        .ok_or_else(|| ParseError::unexpected(input, line, "a `key: value` record"))
+}
+
+/// Every `key: value` record in the non-empty lines of `input`
+pub fn records(input: &str) -> impl Iterator<Item = Result<(&str, &str), ParseError>> {
+    input.lines()
+        .filter(|line| !line.trim().is_empty() )
+        .map(move |line| record(input, line) )
+}
+
+/// Items of a comma separated list, without the surrounding spaces
+pub fn comma_list(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',').map(str::trim)
+}
+
+/// Items of a space separated list
+pub fn space_list(text: &str) -> impl Iterator<Item = &str> {
+    text.split_ascii_whitespace()
+}
+
+/// Parse every item of a list (see `comma_list` and `space_list`), which are slices of `input`
+pub fn parse_list<'a, T, I>(input: &str, items: I, expected: &str) -> Result<Vec<T>, ParseError>
+where T: FromStr,
+      I: IntoIterator<Item = &'a str>
+{
+    items.into_iter()
+        .map(|item| parse_number(input, item, expected) )
+        .collect()
+}
+
+
+// ------------ Grids ------------
+/// Parse a grid of characters where every cell must satisfy `allowed`
+pub fn char_grid<F>(input: &str, allowed: F, expected: &str) -> Result<Vec<Vec<char>>, ParseError>
+where F: Fn(char) -> bool
+{
+    let mut width = None;
+
+    input.lines()
+        .enumerate()
+        .map(|(row, line)| {
+            // Every row must be as wide as the first one
+            let line_width = line.chars().count();
+            let width = *width.get_or_insert(line_width);
+            if line_width != width {
+                return Err(ParseError::Unexpected(row + 1, line_width.min(width) + 1,
+                    format!("a row of {width} characters"), format!("a row of {line_width} characters")));
+            }
+
+            line.chars()
+                .enumerate()
+                .map(|(col, ch)| {
+                    if allowed(ch) { Ok(ch) }
+                    else { Err(ParseError::Unexpected(row + 1, col + 1, expected.to_string(), ch.to_string())) }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "ab\ncde\nf";
+        assert_eq!(location(input, &input[0..]), (1, 1));
+        assert_eq!(location(input, &input[4..]), (2, 2));
+        assert_eq!(location(input, &input[7..]), (3, 1));
+    }
+
+    #[test]
+    fn test_at_line() {
+        let error = ParseError::unexpected("1 x", &"1 x"[2..], "a number");
+        assert_eq!(error.at_line(7), ParseError::Unexpected(7, 3, "a number".to_string(), "x".to_string()));
+        assert_eq!(ParseError::missing("a").at_line(7), ParseError::missing("a"));
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(sections("a\nb\n\nc\n").collect::<Vec<_>>(), vec!["a\nb", "c"]);
+        assert_eq!(sections("a\r\nb\r\n\r\nc\r\n").collect::<Vec<_>>(), vec!["a\r\nb", "c"]);
+        assert_eq!(sections("\n\na\n\n\n\nb").collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(sections("").count(), 0);
+    }
+
+    #[test]
+    fn test_split_sections() {
+        assert_eq!(split_sections("a\n\nb", "parts"), Ok(("a", "b")));
+        assert_eq!(split_sections("a\r\n\r\nb", "parts"), Ok(("a", "b")));
+        assert_eq!(split_sections("a\nb", "parts"), Err(ParseError::missing("empty line separating parts")));
+        assert_eq!(
+            split_sections("a\n\nb\n\nc", "parts"),
+            Err(ParseError::Unexpected(5, 1, "the end of the input".to_string(), "c".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let input = "12 x4";
+        assert_eq!(parse_number::<u32>(input, &input[0..2], "a number"), Ok(12));
+        assert_eq!(
+            parse_number::<u32>(input, &input[3..5], "a number"),
+            Err(ParseError::Unexpected(1, 4, "a number".to_string(), "x4".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        let input = "p=0,4 v=3,-3";
+        assert_eq!(signed_integers::<i32>(input, input).collect::<Result<Vec<_>, _>>(), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(unsigned_integers::<u32>(input, input).collect::<Result<Vec<_>, _>>(), Ok(vec![0, 4, 3, 3]));
+        let too_big = "x 1\ny=300";
+        assert_eq!(
+            unsigned_integers::<u8>(too_big, too_big).collect::<Result<Vec<_>, _>>(),
+            Err(ParseError::Unexpected(2, 3, "a number that fits its type".to_string(), "300".to_string()))
+        );
+        assert_eq!(exactly::<i32, 4>(signed_integers(input, input), input, "4 numbers"), Ok([0, 4, 3, -3]));
+        assert_eq!(
+            exactly::<i32, 3>(signed_integers(input, input), input, "3 numbers"),
+            Err(ParseError::Unexpected(1, 1, "3 numbers".to_string(), input.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_records() {
+        let input = "Register A: 729\n\nProgram: 0,1";
+        assert_eq!(records(input).collect::<Result<Vec<_>, _>>(), Ok(vec![("Register A", "729"), ("Program", "0,1")]));
+        assert_eq!(
+            records("Register A 729").collect::<Result<Vec<_>, _>>(),
+            Err(ParseError::Unexpected(1, 1, "a `key: value` record".to_string(), "Register A 729".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        let input = "1, 2,3\n4 5  6";
+        let (commas, spaces) = input.split_once('\n').unwrap();
+        assert_eq!(parse_list::<u32, _>(input, comma_list(commas), "a number"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_list::<u32, _>(input, space_list(spaces), "a number"), Ok(vec![4, 5, 6]));
+        let empty_item = "1,,2";
+        assert_eq!(
+            parse_list::<u32, _>(empty_item, comma_list(empty_item), "a number"),
+            Err(ParseError::Unexpected(1, 3, "a number".to_string(), "".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_char_grid() {
+        assert_eq!(char_grid("#.\n.#", |c| "#.".contains(c), "`#` or `.`"), Ok(vec![vec!['#', '.'], vec!['.', '#']]));
+        assert_eq!(
+            char_grid("#.\n.x", |c| "#.".contains(c), "`#` or `.`"),
+            Err(ParseError::Unexpected(2, 2, "`#` or `.`".to_string(), "x".to_string()))
+        );
+        assert_eq!(
+            char_grid("#.\n.", |c| "#.".contains(c), "`#` or `.`"),
+            Err(ParseError::Unexpected(2, 2, "a row of 2 characters".to_string(), "a row of 1 characters".to_string()))
+        );
+    }
+}
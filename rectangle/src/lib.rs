@@ -3,27 +3,40 @@ use std::fmt;
 use std::error::Error;
 use std::marker::PhantomData;
 
+use parsing::ParseError;
+
 
 // ------------ RectangleError class ------------
 #[derive(PartialEq, Debug)]
 pub enum RectangleError {
     DifferentWidths,
-    InvalidCharacter(usize, usize, char)
+    /// Row, column, the character found, and what was expected instead
+    InvalidCharacter(usize, usize, char, &'static str)
 }
 
 impl fmt::Display for RectangleError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             RectangleError::DifferentWidths => write!(f, "Rows have different widths."),
-            RectangleError::InvalidCharacter(row,col, ch) => write!(f, "Invalid character `{ch}`found while parsing at row {row}, column {col}."),
+            RectangleError::InvalidCharacter(row,col, ch, expected) => write!(f, "Invalid character `{ch}` found while parsing at row {row}, column {col}, expected {expected}."),
         }
     }
 }
 impl Error for RectangleError {}
 
+impl From<RectangleError> for ParseError {
+    fn from(error: RectangleError) -> Self {
+        match error {
+            RectangleError::InvalidCharacter(row, col, ch, expected) =>
+                ParseError::Unexpected(row + 1, col + 1, expected.to_string(), ch.to_string()),
+            RectangleError::DifferentWidths => ParseError::Invalid(error.to_string()),
+        }
+    }
+}
+
 
 // ------------ Rectangle class ------------
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rectangle<T> {
     pub data: Vec<Vec<T>>,
     pub height: usize,
@@ -70,7 +83,7 @@ impl<T> Rectangle<T> {
                     .enumerate()
                     .map( |(col, c)| {
                         match c.to_digit(10) {
-                            None => Err(RectangleError::InvalidCharacter(row, col, c)),
+                            None => Err(RectangleError::InvalidCharacter(row, col, c, "a digit")),
                             Some(n) => Ok(T::from(n))
                         }
                     })
@@ -98,12 +111,6 @@ impl Rectangle<char> {
         Rectangle::from_vec(data)
     }
 
-    pub fn to_string(&self) -> String {
-        self.data.iter()
-            .flat_map(|row| row.iter().chain(Some('\n').iter()) )
-            .collect::<String>()
-    }
-
     /// Same as to_string but can modify each individual character via closure
     /// that takes the cell coordinates and the current character
     pub fn to_string_with_changes<F>(&self, f: F) -> String
@@ -122,6 +129,16 @@ impl Rectangle<char> {
 }
 
 
+impl fmt::Display for Rectangle<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.data.iter()
+            .flat_map(|row| row.iter().chain(Some('\n').iter()) )
+            .collect::<String>();
+        write!(f, "{text}")
+    }
+}
+
+
 // ------------ Edge class ------------
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum RectangleEdge {
@@ -222,6 +239,16 @@ impl Direction {
             Direction::Right  => Direction::Left,
         }
     }
+
+    /// Direction after a quarter turn clockwise
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up    => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down  => Direction::Left,
+            Direction::Left  => Direction::Up,
+        }
+    }
 }
 
 // ------------ Iterator helper ------------
@@ -476,7 +503,7 @@ pub trait RectangularData<T: std::cmp::PartialEq + Copy + Clone>: Rectangular {
     /// Return an iterator that provides a tuple containing both of the following:
     /// 1. the coordinate (row, col), and
     /// 2. a reference to the character
-    fn iter_coord_and_data(&self) -> RectangularDataIterator<Self, T> {
+    fn iter_coord_and_data(&self) -> RectangularDataIterator<'_, Self, T> {
         RectangularDataIterator::new(self.get_width(), self.get_height(), self)
     }
 
@@ -488,7 +515,7 @@ pub trait RectangularData<T: std::cmp::PartialEq + Copy + Clone>: Rectangular {
     /// and reach a distance (D) in all directions, so the returned elements are in the
     /// rectangle given by \[(C-D, C-D), (C+D+1, C+D+1)\] but are clipped to not exceed
     /// the size of the rectangle in any direction.
-    fn iter_coord_and_data_around_coord(&self, coord: &(usize, usize), distance: usize) -> SubRectangularDataIterator<Self, T> {
+    fn iter_coord_and_data_around_coord(&self, coord: &(usize, usize), distance: usize) -> SubRectangularDataIterator<'_, Self, T> {
         let start_row = coord.0 - cmp::min(coord.0, distance);
         let start_col = coord.1 - cmp::min(coord.1, distance);
         let one_past_end_row = cmp::min(coord.0 + distance + 1, self.get_height());
@@ -594,7 +621,9 @@ ghi";
 000";
         let rectangle_result: RectangleResult<u32> = Rectangle::from_num_str(input);
 
-        assert_eq!(rectangle_result.unwrap_err(), RectangleError::InvalidCharacter(1, 2, 'a'));
+        let error = rectangle_result.unwrap_err();
+        assert_eq!(error, RectangleError::InvalidCharacter(1, 2, 'a', "a digit"));
+        assert_eq!(ParseError::from(error), ParseError::Unexpected(2, 3, "a digit".to_string(), "a".to_string()));
     }
 
     #[test]
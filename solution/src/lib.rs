@@ -0,0 +1,272 @@
+use std::fmt;
+
+use parsing::records;
+pub use parsing::ParseError;
+
+
+// ------------ Answer class ------------
+/// The answer to one part of a puzzle.
+///
+/// Days return different types (counts, sums that need 64 bits, comma separated
+/// strings...), so they are converted into this enum to be handled uniformly.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    /// A part that could not be solved on this input, and why
+    Unsolved(String)
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // Forward the formatter so that width and alignment are honoured
+            Answer::Signed(n)        => fmt::Display::fmt(n, f),
+            Answer::Unsigned(n)      => fmt::Display::fmt(n, f),
+            Answer::Text(txt)        => f.pad(txt),
+            Answer::Unsolved(reason) => f.pad(&format!("unsolved ({reason})")),
+        }
+    }
+}
+
+impl Answer {
+    /// Name of the variant, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Signed(_)   => "signed",
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Text(_)     => "text",
+            Answer::Unsolved(_) => "unsolved",
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Answer::Unsolved(_))
+    }
+}
+
+impl From<i32>    for Answer { fn from(n: i32)    -> Self { Answer::Signed(n as i64) } }
+impl From<i64>    for Answer { fn from(n: i64)    -> Self { Answer::Signed(n) } }
+impl From<u32>    for Answer { fn from(n: u32)    -> Self { Answer::Unsigned(n as u64) } }
+impl From<u64>    for Answer { fn from(n: u64)    -> Self { Answer::Unsigned(n) } }
+impl From<usize>  for Answer { fn from(n: usize)  -> Self { Answer::Unsigned(n as u64) } }
+impl From<String> for Answer { fn from(txt: String) -> Self { Answer::Text(txt) } }
+impl From<&str>   for Answer { fn from(txt: &str)   -> Self { Answer::Text(txt.to_string()) } }
+
+/// Parts which can fail on an input that parses are unsolved with the error as the reason
+impl<T: Into<Answer>, E: fmt::Display> From<Result<T, E>> for Answer {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(answer) => answer.into(),
+            Err(error) => Answer::Unsolved(error.to_string()),
+        }
+    }
+}
+
+
+// ------------ Solution trait ------------
+/// Common interface implemented by every day.
+///
+/// The puzzle text is parsed once into `Input`, and both parts work on it.
+/// `Params` holds any extra knob a day needs (ie: grid sizes that differ between
+/// the sample and the real input). Its `Default` must match the real input.
+pub trait Solution {
+    type Input;
+    type Params: Default;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input, params: &Self::Params) -> Answer;
+    fn part2(input: &Self::Input, params: &Self::Params) -> Answer;
+
+    /// Read the params given by a fixture (ie: `11 7`). Days with params override it,
+    /// the others only accept an empty text.
+    fn params(text: &str) -> Result<Self::Params, ParseError> {
+        match text.trim() {
+            ""    => Ok(Self::Params::default()),
+            other => Err(ParseError::Invalid(format!("This day takes no params, found `{other}`"))),
+        }
+    }
+}
+
+/// Parse the input and solve both parts using the default parameters
+pub fn solve<S: Solution>(input: &str) -> Result<(Answer, Answer), ParseError> {
+    solve_with::<S>(input, &S::Params::default())
+}
+
+/// Parse the input and solve both parts using the given parameters
+pub fn solve_with<S: Solution>(input: &str, params: &S::Params) -> Result<(Answer, Answer), ParseError> {
+    let parsed = S::parse(input)?;
+    Ok( (S::part1(&parsed, params), S::part2(&parsed, params)) )
+}
+
+
+// ------------ Fixtures ------------
+/// Expected answers, and params if any, of one input of a day
+#[derive(PartialEq, Debug, Default, Clone)]
+pub struct Fixture {
+    pub name: String,
+    pub params: Option<String>,
+    pub answers: [Option<String>; 2]
+}
+
+/// Name of the fixture holding the real input
+pub static INPUT_FIXTURE: &str = "input";
+
+/// Read the fixtures of a day, as stored in `data/answers.txt`, in order of appearance.
+/// The `input` fixture is the real input, any other one is read from `data/<name>.txt`:
+///
+/// ```text
+/// sample params: 11 7
+/// sample part1: 12
+/// input part1: 222208000
+/// ```
+pub fn fixtures(text: &str) -> Result<Vec<Fixture>, ParseError> {
+    let mut fixtures: Vec<Fixture> = Vec::new();
+
+    for record in records(text) {
+        let (key, value) = record?;
+        let (name, field) = key.split_once(' ')
+            .ok_or_else(|| ParseError::unexpected(text, key, "`<fixture> <part1|part2|params>`"))?;
+
+        let index = match fixtures.iter().position(|fixture| fixture.name == name) {
+            Some(index) => index,
+            None => {
+                fixtures.push(Fixture { name: name.to_string(), ..Default::default() });
+                fixtures.len() - 1
+            }
+        };
+        let slot = match field {
+            "part1"  => &mut fixtures[index].answers[0],
+            "part2"  => &mut fixtures[index].answers[1],
+            "params" => &mut fixtures[index].params,
+            _ => return Err(ParseError::unexpected(text, key, "`<fixture> <part1|part2|params>`")),
+        };
+        *slot = Some(value.trim().to_string());
+    }
+
+    Ok(fixtures)
+}
+
+/// Solve a fixture with its params and compare with the expected answers.
+/// Parts without an expected answer are not run. Panics on any mismatch, as it is meant for tests.
+pub fn check_fixture<S: Solution>(name: &str, input: &str, answers: &str) {
+    let fixtures = fixtures(answers).unwrap_or_else(|error| panic!("Invalid answers file: {error}"));
+    let fixture = fixtures.iter()
+        .find(|fixture| fixture.name == name)
+        .unwrap_or_else(|| panic!("No answers for `{name}` in the answers file"));
+
+    let params = match &fixture.params {
+        Some(text) => S::params(text).unwrap_or_else(|error| panic!("Invalid params for `{name}`: {error}")),
+        None       => S::Params::default(),
+    };
+    let parsed = S::parse(input).unwrap_or_else(|error| panic!("Cannot parse `{name}`: {error}"));
+
+    let [part1, part2] = &fixture.answers;
+    assert!(part1.is_some() || part2.is_some(), "No answers for `{name}` in the answers file");
+    if let Some(expected) = part1 {
+        assert_eq!(&S::part1(&parsed, &params).to_string(), expected, "`{name}` part 1");
+    }
+    if let Some(expected) = part2 {
+        assert_eq!(&S::part2(&parsed, &params).to_string(), expected, "`{name}` part 2");
+    }
+}
+
+/// Generate a `#[test]` per fixture of a day, checking `data/<fixture>.txt` against `data/answers.txt`:
+///
+/// ```ignore
+/// solution::fixture_tests!(Day12: sample, sample_ab, sample_xo);
+/// ```
+#[macro_export]
+macro_rules! fixture_tests {
+    ($solution:ty: $($fixture:ident),+ $(,)?) => {
+        mod fixtures {
+            use super::*;
+            $(
+                #[test]
+                fn $fixture() {
+                    $crate::check_fixture::<$solution>(
+                        stringify!($fixture),
+                        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/", stringify!($fixture), ".txt")),
+                        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/answers.txt")),
+                    );
+                }
+            )+
+        }
+    };
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<String>;
+        type Params = ();
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            if input.is_empty() {
+                return Err(ParseError::missing("lines"));
+            }
+            Ok( input.lines().map(str::to_string).collect() )
+        }
+
+        fn part1(input: &Self::Input, _: &()) -> Answer { input.len().into() }
+        fn part2(input: &Self::Input, _: &()) -> Answer { input.join(",").into() }
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3_i32).to_string(), "-3");
+        assert_eq!(Answer::from(12_usize).to_string(), "12");
+        assert_eq!(Answer::from("6,1").to_string(), "6,1");
+        assert_eq!(format!("{:>5}|{:<4}|", Answer::from(-3_i32), Answer::from("6,1")), "   -3|6,1 |");
+        assert_eq!(Answer::from(Ok::<_, ParseError>(4_u32)), Answer::Unsigned(4));
+        assert_eq!(Answer::from(Err::<u32, _>(ParseError::missing("path"))).to_string(), "unsolved (Missing path.)");
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve::<Lines>("a\nb"), Ok( (Answer::Unsigned(2), Answer::Text("a,b".to_string())) ));
+        assert!(solve::<Lines>("").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        assert_eq!(Lines::params(""), Ok(()));
+        assert!(Lines::params("11 7").is_err());
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let fixtures = fixtures("sample params: 7\nsample part1: 11\n\ninput part2: 6,1\nsample part2: 3\n").unwrap();
+        assert_eq!(fixtures, vec![
+            Fixture { name: "sample".to_string(), params: Some("7".to_string()), answers: [Some("11".to_string()), Some("3".to_string())] },
+            Fixture { name: "input".to_string(), params: None, answers: [None, Some("6,1".to_string())] },
+        ]);
+
+        assert_eq!(
+            super::fixtures("sample part1: 11\nsample part3: 1"),
+            Err(ParseError::Unexpected(2, 1, "`<fixture> <part1|part2|params>`".to_string(), "sample part3".to_string()))
+        );
+        assert!(super::fixtures("sample: 1").is_err());
+    }
+
+    #[test]
+    fn test_check_fixture() {
+        check_fixture::<Lines>("two", "a\nb", "two part1: 2\ntwo part2: a,b\n");
+        check_fixture::<Lines>("two", "a\nb", "two part2: a,b\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "`two` part 1")]
+    fn test_check_fixture_wrong() {
+        check_fixture::<Lines>("two", "a\nb", "two part1: 3\n");
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::sync::RwLock;
+
+/// Whether tracing is compiled in. When it is not, the macros expand to `if false { ... }`
+/// and their arguments are never evaluated.
+pub const ENABLED: bool = cfg!(feature = "enabled");
+
+
+// ------------ Level class ------------
+/// How detailed an event is, from a few lines per run to one per step of a loop
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Level {
+    Info,
+    Debug,
+    Trace
+}
+
+impl Level {
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name {
+            "info"  => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _       => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Info  => f.pad("info"),
+            Level::Debug => f.pad("debug"),
+            Level::Trace => f.pad("trace"),
+        }
+    }
+}
+
+
+// ------------ Filter class ------------
+/// A crate whose events are printed, up to a level of detail
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Filter {
+    /// Crate name (ie: `day9`), or `all`
+    pub target: String,
+    pub level: Level
+}
+
+impl Filter {
+    /// Parse `day9` or `day9=trace`. The level defaults to debug.
+    pub fn parse(text: &str) -> Result<Filter, String> {
+        let (target, level) = match text.split_once('=') {
+            Some((target, name)) => (target, Level::from_name(name)
+                .ok_or_else(|| format!("Trace level `{name}` must be `info`, `debug` or `trace`"))?),
+            None                 => (text, Level::Debug),
+        };
+
+        match target {
+            "" => Err(format!("Missing day in trace filter `{text}`")),
+            _  => Ok( Filter { target: target.to_string(), level } ),
+        }
+    }
+
+    fn matches(&self, target: &str, level: Level) -> bool {
+        (self.target == "all" || self.target == target) && level <= self.level
+    }
+}
+
+static FILTERS: RwLock<Vec<Filter>> = RwLock::new(Vec::new());
+
+/// Replace the filters, usually once when the runner starts
+pub fn set_filters(filters: Vec<Filter>) {
+    *FILTERS.write().unwrap() = filters;
+}
+
+/// Crate name of a module path, ie: `day11` for `day11::lib_shortcuts`
+fn target(module_path: &str) -> &str {
+    module_path.split("::").next().unwrap_or(module_path)
+}
+
+/// Whether some filter lets the events of a module and level through
+pub fn enabled(module_path: &str, level: Level) -> bool {
+    FILTERS.read().unwrap().iter().any(|filter| filter.matches(target(module_path), level))
+}
+
+/// Print an event on stderr, so that it does not mix with the answers
+pub fn emit(module_path: &str, level: Level, args: fmt::Arguments) {
+    eprintln!("[{} {level}] {args}", target(module_path));
+}
+
+
+// ------------ Macros ------------
+/// Print an event of the given level, if tracing is compiled in and the calling crate is traced
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::ENABLED && $crate::enabled(module_path!(), $level) {
+            $crate::emit(module_path!(), $level, format_args!($($arg)+));
+        }
+    };
+}
+
+/// A few events per run, ie: the size of the search space
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::event!($crate::Level::Info, $($arg)+) };
+}
+
+/// Meaningful points of a solver, ie: a broken rule or a moved block
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::event!($crate::Level::Debug, $($arg)+) };
+}
+
+/// Every step of a loop, or whole drawings of the state
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::event!($crate::Level::Trace, $($arg)+) };
+}
+
+
+
+// ------------ Tests ------------
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(Filter::parse("day9"), Ok(Filter { target: "day9".to_string(), level: Level::Debug }));
+        assert_eq!(Filter::parse("all=trace"), Ok(Filter { target: "all".to_string(), level: Level::Trace }));
+        assert_eq!(Filter::parse("day9=loud"), Err("Trace level `loud` must be `info`, `debug` or `trace`".to_string()));
+        assert_eq!(Filter::parse("=info"), Err("Missing day in trace filter `=info`".to_string()));
+    }
+
+    #[test]
+    fn test_enabled() {
+        set_filters(vec![Filter::parse("day9=info").unwrap(), Filter::parse("day11").unwrap()]);
+        assert!(enabled("day9", Level::Info));
+        assert!(!enabled("day9", Level::Debug));
+        assert!(enabled("day11::lib_shortcuts", Level::Debug));
+        assert!(!enabled("day11::lib_shortcuts", Level::Trace));
+        assert!(!enabled("day1", Level::Info));
+
+        set_filters(vec![Filter::parse("all=trace").unwrap()]);
+        assert!(enabled("day1", Level::Trace));
+    }
+}